@@ -0,0 +1,95 @@
+//! Compares the time taken to initialize arrays with this crate and with the
+//! standard library, which shows whether the array is copied around once
+//! initialized.
+//!
+//! Run with `cargo bench`.
+//!
+//! The functions compared against `core::array::from_fn` are not inlined, so
+//! that their assembly can be compared as well. Emit it with
+//!
+//! ```sh
+//! cargo rustc --release --bench init -- --emit asm
+//! ```
+//!
+//! and look for the functions in `target/release/deps/init-*.s`. With rustc
+//! 1.95 on x86-64, the `array_init_*` and `from_fn_*` functions of each pair
+//! are identical once labels are renamed: the elements are written into a
+//! local array, which a single `memcpy` then moves into the return slot. No
+//! way of returning the array by value avoids this copy on stable Rust.
+//! `array_init_in_place_u64` has no `memcpy`: the elements are written straight
+//! into the slot it is given.
+
+use std::hint::black_box;
+use std::mem::MaybeUninit;
+use std::sync::atomic::AtomicU64;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 100_000;
+
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    // Warm up
+    for _ in 0..ITERATIONS / 10 {
+        black_box(f());
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    println!("{:<32}{:>12?}", name, start.elapsed() / ITERATIONS);
+}
+
+#[inline(never)]
+fn array_init_u64() -> [u64; 1024] {
+    array_init::array_init(|i| black_box(i as u64))
+}
+
+#[inline(never)]
+fn from_fn_u64() -> [u64; 1024] {
+    core::array::from_fn(|i| black_box(i as u64))
+}
+
+#[inline(never)]
+fn array_init_in_place_u64(slot: &mut MaybeUninit<[u64; 1024]>) {
+    array_init::array_init_in_place(slot, |i| black_box(i as u64));
+}
+
+#[inline(never)]
+fn array_init_drop() -> [Option<Box<u64>>; 1024] {
+    array_init::array_init(|_| black_box(None))
+}
+
+#[inline(never)]
+fn from_fn_drop() -> [Option<Box<u64>>; 1024] {
+    core::array::from_fn(|_| black_box(None))
+}
+
+#[inline(never)]
+fn array_init_atomics() -> [AtomicU64; 64] {
+    array_init::array_init(|i| AtomicU64::new(black_box(i as u64)))
+}
+
+#[inline(never)]
+fn from_fn_atomics() -> [AtomicU64; 64] {
+    core::array::from_fn(|i| AtomicU64::new(black_box(i as u64)))
+}
+
+fn main() {
+    bench("array_init", array_init_u64);
+    bench("core::array::from_fn", from_fn_u64);
+    let mut slot = Box::new(MaybeUninit::uninit());
+    bench("array_init_in_place", || array_init_in_place_u64(&mut slot));
+    bench("from_iter", || {
+        array_init::from_iter::<_, u64, 1024>((0..).map(black_box))
+    });
+    bench("array_init (atomics)", array_init_atomics);
+    bench("core::array::from_fn (atomics)", from_fn_atomics);
+    let bytes = [0x5a_u8; 4096];
+    bench("from_slice_copy", || {
+        array_init::from_slice_copy::<u8, 4096>(black_box(&bytes[..]))
+    });
+    bench("from_iter (copied slice)", || {
+        array_init::from_iter::<_, u8, 4096>(black_box(&bytes[..]).iter().copied())
+    });
+    bench("array_init (drop)", array_init_drop);
+    bench("core::array::from_fn (drop)", from_fn_drop);
+}
@@ -0,0 +1,106 @@
+//! The errors returned by the fallible constructors.
+
+use ::core::{convert::Infallible, fmt};
+
+/// The error returned by the constructors that check the number of elements
+/// given by an iterator, or whose initializer may fail.
+///
+/// `E` is the error type of the initializer, or of the elements of the
+/// iterator. It defaults to [`Infallible`] for the constructors that do not
+/// return [`ArrayInitError::Closure`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArrayInitError<E = Infallible> {
+    /// The initializer, or the iterator, yielded an error.
+    Closure(E),
+    /// The iterator was exhausted before the array was full.
+    TooShort {
+        /// The number of elements the iterator yielded.
+        got: usize,
+        /// The length of the array.
+        expected: usize,
+    },
+    /// The iterator yielded more elements than the array can hold.
+    TooLong {
+        /// The length of the array.
+        expected: usize,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for ArrayInitError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayInitError::Closure(err) => write!(f, "failed to initialize element: {}", err),
+            ArrayInitError::TooShort { got, expected } => write!(
+                f,
+                "expected {} elements, but the iterator yielded {}",
+                expected, got
+            ),
+            ArrayInitError::TooLong { expected } => write!(
+                f,
+                "expected {} elements, but the iterator yielded more",
+                expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> ::std::error::Error for ArrayInitError<E>
+where
+    E: ::std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+        match self {
+            ArrayInitError::Closure(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The error returned by [`from_iters_zip_checked`](crate::from_iters_zip_checked).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ZipError {
+    /// The first iterator was exhausted before the array was full.
+    FirstTooShort {
+        /// The number of pairs that were built.
+        got: usize,
+    },
+    /// The second iterator was exhausted before the array was full.
+    SecondTooShort {
+        /// The number of pairs that were built.
+        got: usize,
+    },
+}
+
+impl fmt::Display for ZipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZipError::FirstTooShort { got } => {
+                write!(f, "the first iterator was exhausted after {} pairs", got)
+            }
+            ZipError::SecondTooShort { got } => {
+                write!(f, "the second iterator was exhausted after {} pairs", got)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ZipError {}
+
+/// The error returned by
+/// [`async_array_init_timeout`](crate::async_array_init_timeout) when the
+/// future of an element does not complete before its timer.
+#[cfg(feature = "async")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeoutError;
+
+#[cfg(feature = "async")]
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the initializer timed out")
+    }
+}
+
+#[cfg(all(feature = "async", feature = "std"))]
+impl ::std::error::Error for TimeoutError {}
@@ -14,6 +14,27 @@
 //! Care is taken not to leak memory shall the initialization
 //! fail.
 //!
+//! # Panics
+//!
+//! Unless documented otherwise, the functions of this crate never
+//! panic on their own: the only panics come from the closures,
+//! iterators and `Drop` implementations they are given. Elements
+//! are written through pointers that are in bounds by construction,
+//! so there are no bounds checks to fail.
+//!
+//! If such a panic unwinds through one of the functions, the
+//! elements initialized so far are dropped, in order from lower
+//! to higher indices, unless the `leak-on-panic` feature is
+//! enabled. This is also the case for the functions
+//! initializing from the last element to the first, such as
+//! `array_init_rev`, which thus drop the elements in the reverse
+//! order of their initialization. The functions producing several
+//! arrays, such as `array_init_2d`, drop the elements of each
+//! array in this order, but the order across arrays is
+//! unspecified, as is the order for `array_init_par`. With
+//! `panic = "abort"`, they are not dropped, since the process
+//! aborts right away.
+//!
 //! # Examples:
 //! ```rust
 //! # #![allow(unused)]
@@ -43,11 +64,73 @@
 //!     this
 //! });
 //! ```
+//!
+//! # Cargo features
+//!
+//!   - `alloc`: enables the constructors returning a `Box` or taking a `Vec`,
+//!     such as `array_init_boxed`, `try_array_init_boxed` and `from_vec`.
+//!   - `async`: enables `async_array_init` and `async_array_init_timeout`, which
+//!     await the futures returned by the initializer.
+//!   - `bytemuck`: enables `from_bytes`, which copies bytes into arrays of plain
+//!     old data.
+//!   - `futures`: enables `from_stream`, which fills arrays from the items of a
+//!     stream.
+//!   - `rand`: enables `array_init_random` and `array_init_sample`, which fill
+//!     arrays with random values.
+//!   - `rayon`: enables `array_init_par`, which initializes arrays in parallel.
+//!   - `serde`: enables `deserialize_array`, which deserializes arrays of any
+//!     length and element type.
+//!   - `leak-on-panic`: leaks the elements initialized so far, instead of
+//!     dropping them, when an initializer panics. This saves keeping track of
+//!     them, which makes the code smaller, and is meant for programs built
+//!     with `panic = "abort"`, where they would not be dropped anyway.
+//!     **Beware**: with unwinding panics, this leaks memory and skips `Drop`
+//!     implementations, which may release other resources. Elements are still
+//!     dropped when an initializer returns an error. This only affects
+//!     [`array_init`], [`try_array_init`] and most of the functions built on
+//!     them; see [`uses_fast_path`].
+//!   - `std`: enables `array_init_catch`, which catches panics of the
+//!     initializer, and implements `std::error::Error` for the error types.
+//!     Implies `alloc`.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+#[cfg(feature = "alloc")]
+use ::alloc::{alloc::Layout, boxed::Box, vec::Vec};
+#[cfg(feature = "bytemuck")]
+use ::bytemuck::Pod;
+#[cfg(any(feature = "alloc", feature = "async", feature = "futures"))]
+use ::core::pin::Pin;
 use ::core::{
+    convert::TryFrom,
+    fmt, hint,
+    iter::Peekable,
+    marker::PhantomData,
     mem::{self, MaybeUninit},
-    ptr, slice,
+    ops::Range,
+    ptr,
+};
+#[cfg(any(feature = "async", feature = "futures"))]
+use ::core::{
+    future::Future,
+    task::{Context, Poll},
 };
+#[cfg(feature = "futures")]
+use ::futures::Stream;
+#[cfg(feature = "std")]
+use ::std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
+
+mod error;
+
+#[cfg(feature = "async")]
+pub use error::TimeoutError;
+pub use error::{ArrayInitError, ZipError};
 
 #[inline]
 /// Initialize an array given an initializer expression.
@@ -55,6 +138,16 @@ use ::core::{
 /// The initializer is given the index of the element. It is allowed
 /// to mutate external state; we will always initialize the elements in order.
 ///
+/// The array is built in a local and then returned by value, which compiles to
+/// a `memcpy` into the caller's return slot, as for `core::array::from_fn`.
+/// To write the elements straight into memory the caller owns, e.g., for large
+/// arrays, use [`array_init_in_place`].
+///
+/// # Panics
+///
+/// Panics only if the initializer panics, in which case the elements
+/// initialized so far are dropped.
+///
 /// # Examples
 ///
 /// ```rust
@@ -67,30 +160,98 @@ use ::core::{
 ///
 /// assert!(arr.iter().enumerate().all(|(i, &x)| x == i * i));
 /// ```
-pub fn array_init<F, T, const N: usize>(mut initializer: F) -> [T; N]
+///
+/// To initialize an array of trait objects, annotate the return type of the
+/// initializer, so that each element is coerced to the trait object:
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use std::fmt::Display;
+///
+/// let arr: [Box<dyn Display>; 2] = array_init::array_init(|i| -> Box<dyn Display> {
+///     if i == 0 {
+///         Box::new("zero")
+///     } else {
+///         Box::new(i)
+///     }
+/// });
+///
+/// assert_eq!(arr[0].to_string(), "zero");
+/// ```
+pub fn array_init<F, T, const N: usize>(initializer: F) -> [T; N]
 where
     F: FnMut(usize) -> T,
 {
-    enum Unreachable {}
+    array_init_impl::<F, T, N, 1>(initializer)
+}
 
-    try_array_init(
-        // monomorphise into an infallible version
-        move |i| -> Result<T, Unreachable> { Ok(initializer(i)) },
-    )
-    .unwrap_or_else(
-        // zero-cost unwrap
-        |unreachable| match unreachable { /* ! */ },
-    )
+#[inline]
+/// Initialize an array given an initializer expression.
+///
+/// This is the same as [`array_init`], named after `core::array::from_fn` so that
+/// switching between the two is a matter of changing the path.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let arr: [usize; 5] = array_init::from_fn(|i| i * 2);
+///
+/// assert_eq!(arr, [0, 2, 4, 6, 8]);
+/// ```
+pub fn from_fn<F, T, const N: usize>(initializer: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    array_init(initializer)
 }
 
 #[inline]
-/// Initialize an array given an iterator
+/// Initialize an array given an initializer expression that does not mutate
+/// its captures.
 ///
-/// We will iterate until the array is full or the iterator is exhausted. Returns
-/// `None` if the iterator is exhausted before we can fill the array.
+/// This is the same as [`array_init`], but the initializer is an [`Fn`], which
+/// states that it does not keep state between calls. A reference to it is
+/// still an [`Fn`], so the same initializer can be reused for several arrays,
+/// or shared with `array_init_par` when the `rayon` feature is enabled.
 ///
-///   - Once the array is full, extra elements from the iterator (if any)
-///     won't be consumed.
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let offset = 10;
+/// let shifted = |i| i + offset;
+///
+/// let first: [usize; 3] = array_init::array_init_fn(&shifted);
+/// let second: [usize; 5] = array_init::array_init_fn(&shifted);
+///
+/// assert_eq!(first, [10, 11, 12]);
+/// assert_eq!(second, [10, 11, 12, 13, 14]);
+/// ```
+pub fn array_init_fn<F, T, const N: usize>(initializer: F) -> [T; N]
+where
+    F: Fn(usize) -> T,
+{
+    array_init(initializer)
+}
+
+#[inline]
+/// Initialize an array given an initializer function pointer.
+///
+/// This is the same as [`array_init`], but since the initializer is a function
+/// pointer rather than a generic closure, the function is instantiated once
+/// per element type and length, whichever initializer is passed, which keeps
+/// the code small when many tables are built.
+///
+/// Calling function pointers is not allowed in `const` contexts. There, pass the
+/// path of a `const fn` to [`const_array_init!`] instead, which evaluates the
+/// same table at compile time.
 ///
 /// # Examples
 ///
@@ -98,33 +259,111 @@ where
 /// # #![allow(unused)]
 /// # extern crate array_init;
 /// #
-/// // Initialize an array from an iterator
-/// // producing an array of [1,2,3,4] repeated
+/// fn opcode_name(i: usize) -> &'static str {
+///     ["nop", "load", "store", "jump"].get(i).copied().unwrap_or("invalid")
+/// }
 ///
-/// let four = [1,2,3,4];
-/// let mut iter = four.iter().copied().cycle();
-/// let arr: [u32; 10] = array_init::from_iter(iter).unwrap();
-/// assert_eq!(arr, [1, 2, 3, 4, 1, 2, 3, 4, 1, 2]);
+/// let names: [&str; 6] = array_init::array_init_fn_table(opcode_name);
+/// assert_eq!(names, ["nop", "load", "store", "jump", "invalid", "invalid"]);
 /// ```
-pub fn from_iter<Iterable, T, const N: usize>(iterable: Iterable) -> Option<[T; N]>
+///
+/// The same table as a `static`, built at compile time:
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # #[macro_use]
+/// # extern crate array_init;
+/// #
+/// const fn opcode_name(i: usize) -> &'static str {
+///     match i {
+///         0 => "nop",
+///         1 => "load",
+///         2 => "store",
+///         3 => "jump",
+///         _ => "invalid",
+///     }
+/// }
+///
+/// static NAMES: [&str; 6] = array_init::const_array_init!(6, opcode_name);
+///
+/// assert_eq!(NAMES, array_init::array_init_fn_table::<_, 6>(opcode_name));
+/// ```
+pub fn array_init_fn_table<T, const N: usize>(initializer: fn(usize) -> T) -> [T; N] {
+    array_init(initializer)
+}
+
+#[inline]
+/// Initialize an array given an initializer expression, from the last element
+/// to the first.
+///
+/// The initializer is given the index of the element. It is allowed to mutate
+/// external state; we will always initialize from higher to lower indices.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // Initialize an array with the suffix sums of another one
+/// let values = [1, 2, 3, 4];
+/// let mut sum = 0;
+/// let suffix_sums: [u32; 4] = array_init::array_init_rev(|i| {
+///     sum += values[i];
+///     sum
+/// });
+/// assert_eq!(suffix_sums, [10, 9, 7, 4]);
+/// ```
+pub fn array_init_rev<F, T, const N: usize>(mut initializer: F) -> [T; N]
 where
-    Iterable: IntoIterator<Item = T>,
+    F: FnMut(usize) -> T,
 {
-    try_array_init_impl::<_, _, T, N, 1>({
-        let mut iterator = iterable.into_iter();
-        move |_| iterator.next().ok_or(())
-    })
-    .ok()
+    array_init_impl::<_, T, N, -1>(move |i| initializer(N - 1 - i))
 }
 
 #[inline]
-/// Initialize an array in reverse given an iterator
+/// Initialize an array given an initializer expression, in the direction given
+/// by `REVERSED`.
 ///
-/// We will iterate until the array is full or the iterator is exhausted. Returns
-/// `None` if the iterator is exhausted before we can fill the array.
+/// This is [`array_init`] if `REVERSED` is `false`, and [`array_init_rev`]
+/// otherwise, so that generic code can be parametrized over the direction. The
+/// choice is made at compile time.
 ///
-///   - Once the array is full, extra elements from the iterator (if any)
-///     won't be consumed.
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// fn visit_order<const REVERSED: bool>() -> [usize; 4] {
+///     let mut calls = 0;
+///     array_init::array_init_dir::<_, _, REVERSED, 4>(|_| {
+///         calls += 1;
+///         calls
+///     })
+/// }
+///
+/// assert_eq!(visit_order::<false>(), [1, 2, 3, 4]);
+/// assert_eq!(visit_order::<true>(), [4, 3, 2, 1]);
+/// ```
+pub fn array_init_dir<F, T, const REVERSED: bool, const N: usize>(initializer: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    if REVERSED {
+        array_init_rev(initializer)
+    } else {
+        array_init(initializer)
+    }
+}
+
+#[inline]
+/// Initialize an array given an initial state and an initializer expression
+/// updating it.
+///
+/// The initializer is given the index of the element and a mutable reference to
+/// the state, which it may update. We will always initialize the elements in
+/// order, so the state seen for index `i` is the one left by index `i - 1`.
 ///
 /// # Examples
 ///
@@ -132,30 +371,28 @@ where
 /// # #![allow(unused)]
 /// # extern crate array_init;
 /// #
-/// // Initialize an array from an iterator
-/// // producing an array of [4,3,2,1] repeated, finishing with 1.
+/// let fibonacci: [u64; 8] = array_init::array_init_scan((0, 1), |_, (secondlast, last)| {
+///     let this = *secondlast + *last;
+///     *secondlast = *last;
+///     *last = this;
+///     this
+/// });
 ///
-/// let four = [1,2,3,4];
-/// let mut iter = four.iter().copied().cycle();
-/// let arr: [u32; 10] = array_init::from_iter_reversed(iter).unwrap();
-/// assert_eq!(arr, [2, 1, 4, 3, 2, 1, 4, 3, 2, 1]);
+/// assert_eq!(fibonacci, [1, 2, 3, 5, 8, 13, 21, 34]);
 /// ```
-pub fn from_iter_reversed<Iterable, T, const N: usize>(iterable: Iterable) -> Option<[T; N]>
+pub fn array_init_scan<F, S, T, const N: usize>(mut state: S, mut initializer: F) -> [T; N]
 where
-    Iterable: IntoIterator<Item = T>,
+    F: FnMut(usize, &mut S) -> T,
 {
-    try_array_init_impl::<_, _, T, N, -1>({
-        let mut iterator = iterable.into_iter();
-        move |_| iterator.next().ok_or(())
-    })
-    .ok()
+    array_init(|i| initializer(i, &mut state))
 }
 
 #[inline]
-/// Initialize an array given an initializer expression that may fail.
+/// Initialize an array given an initializer expression that also takes the
+/// length of the array.
 ///
-/// The initializer is given the index (between 0 and `N - 1` included) of the element, and returns a `Result<T, Err>,`. It is allowed
-/// to mutate external state; we will always initialize from lower to higher indices.
+/// The initializer is given the index of the element and `N`, which saves
+/// generic code from naming `N` when it is only inferred.
 ///
 /// # Examples
 ///
@@ -163,38 +400,54 @@ where
 /// # #![allow(unused)]
 /// # extern crate array_init;
 /// #
-/// #[derive(PartialEq,Eq,Debug)]
-/// struct DivideByZero;
+/// let weights: [f64; 4] = array_init::array_init_len(|_, len| 1.0 / len as f64);
 ///
-/// fn inv(i : usize) -> Result<f64,DivideByZero> {
-///     if i == 0 {
-///         Err(DivideByZero)
-///     } else {
-///         Ok(1./(i as f64))
-///     }
-/// }
+/// assert_eq!(weights, [0.25; 4]);
+/// ```
+pub fn array_init_len<F, T, const N: usize>(mut initializer: F) -> [T; N]
+where
+    F: FnMut(usize, usize) -> T,
+{
+    array_init(|i| initializer(i, N))
+}
+
+#[inline]
+/// Initialize an array given an initializer expression, calling a hook before
+/// each element is initialized.
 ///
-/// // If the initializer does not fail, we get an initialized array
-/// let arr: [f64; 3] = array_init::try_array_init(|i| inv(3-i)).unwrap();
-/// assert_eq!(arr,[1./3., 1./2., 1./1.]);
+/// The hook is given the index of the element about to be initialized, e.g., to
+/// report progress or to open a tracing span. A hook that does nothing costs
+/// nothing, since the calls are monomorphized away.
 ///
-/// // The initializer fails
-/// let res : Result<[f64;4], DivideByZero> = array_init::try_array_init(|i| inv(3-i));
-/// assert_eq!(res,Err(DivideByZero));
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let mut started = Vec::new();
+/// let arr: [usize; 3] = array_init::array_init_with_hook(|i| i * 10, |i| started.push(i));
+///
+/// assert_eq!(arr, [0, 10, 20]);
+/// assert_eq!(started, [0, 1, 2]);
 /// ```
-pub fn try_array_init<Err, F, T, const N: usize>(initializer: F) -> Result<[T; N], Err>
+pub fn array_init_with_hook<F, H, T, const N: usize>(mut initializer: F, mut hook: H) -> [T; N]
 where
-    F: FnMut(usize) -> Result<T, Err>,
+    F: FnMut(usize) -> T,
+    H: FnMut(usize),
 {
-    try_array_init_impl::<Err, F, T, N, 1>(initializer)
+    array_init(|i| {
+        hook(i);
+        initializer(i)
+    })
 }
 
 #[inline]
-/// Initialize an array given a source array and a mapping expression. The size of the source array
-/// is the same as the size of the returned array.
+/// Initialize an array given an initializer expression, and check the complete
+/// array.
 ///
-/// The mapper is given an element from the source array and maps it to an element in the
-/// destination.
+/// Once the array is full, it is given to `check`, and is returned if `check`
+/// returns `true`. Otherwise, the array is dropped and `None` is returned.
 ///
 /// # Examples
 ///
@@ -202,197 +455,5998 @@ where
 /// # #![allow(unused)]
 /// # extern crate array_init;
 /// #
-/// // Initialize an array of length 50 containing successive squares
-/// let arr: [usize; 50] = array_init::array_init(|i| i * i);
+/// let sorted = |arr: &[u32; 4]| arr.windows(2).all(|w| w[0] <= w[1]);
 ///
-/// // Map each usize element to a u64 element.
-/// let u64_arr: [u64; 50] = array_init::map_array_init(&arr, |element| *element as u64);
+/// let arr: Option<[u32; 4]> = array_init::array_init_checked(|i| (i * i) as u32, sorted);
+/// assert_eq!(arr, Some([0, 1, 4, 9]));
 ///
-/// assert!(u64_arr.iter().enumerate().all(|(i, &x)| x == (i * i) as u64));
+/// let arr: Option<[u32; 4]> = array_init::array_init_checked(|i| (i % 2) as u32, sorted);
+/// assert_eq!(arr, None);
 /// ```
-pub fn map_array_init<M, T, U, const N: usize>(source: &[U; N], mut mapper: M) -> [T; N]
+pub fn array_init_checked<F, C, T, const N: usize>(initializer: F, check: C) -> Option<[T; N]>
 where
-    M: FnMut(&U) -> T,
+    F: FnMut(usize) -> T,
+    C: FnOnce(&[T; N]) -> bool,
 {
-    // # Safety
-    //   - The array size is known at compile time so we are certain that both the source and
-    //     desitination have the same size. If the two arrays are of the same size we know that a
-    //     valid index for one would be a valid index for the other.
-    array_init(|index| unsafe { mapper(source.get_unchecked(index)) })
+    let array = array_init(initializer);
+    if check(&array) {
+        Some(array)
+    } else {
+        None
+    }
 }
 
 #[inline]
-fn try_array_init_impl<Err, F, T, const N: usize, const D: i8>(
-    mut initializer: F,
-) -> Result<[T; N], Err>
+/// Initialize an array given a shared context and an initializer expression
+/// reading from it.
+///
+/// The initializer is given a reference to the context and the index of the
+/// element. This is the same as capturing `ctx` in the initializer, but keeps
+/// the initializer free of borrows, so it can be written as a plain function.
+/// The elements may borrow from the context.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// fn lookup(table: &[u32], i: usize) -> u32 {
+///     table[i % table.len()]
+/// }
+///
+/// let table = [3, 5];
+/// let arr: [u32; 5] = array_init::array_init_ctx(&table[..], lookup);
+///
+/// assert_eq!(arr, [3, 5, 3, 5, 3]);
+/// ```
+pub fn array_init_ctx<'c, C, F, T, const N: usize>(ctx: &'c C, mut initializer: F) -> [T; N]
 where
-    F: FnMut(usize) -> Result<T, Err>,
+    C: ?Sized,
+    F: FnMut(&'c C, usize) -> T,
 {
-    // The implementation differentiates two cases:
-    //   A) `T` does not need to be dropped. Even if the initializer panics
-    //      or returns `Err` we will not leak memory.
-    //   B) `T` needs to be dropped. We must keep track of which elements have
-    //      been initialized so far, and drop them if we encounter a panic or `Err` midway.
-    if !mem::needs_drop::<T>() {
-        let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
-        // pointer to array = *mut [T; N] <-> *mut T = pointer to first element
-        let mut ptr_i = array.as_mut_ptr() as *mut T;
+    array_init(|i| initializer(ctx, i))
+}
+
+#[inline]
+/// Initialize an array given an initializer expression taking an [`Index`]
+/// token instead of a `usize`.
+///
+/// The token proves that the index is in bounds of any array of length `N`, so
+/// the initializer can read the elements of other arrays of that length
+/// without bounds checks, and without `unsafe`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// fn add<const N: usize>(a: &[u32; N], b: &[u32; N]) -> [u32; N] {
+///     array_init::array_init_token(|i| i.of(a) + i.of(b))
+/// }
+///
+/// assert_eq!(add(&[1, 2, 3], &[10, 20, 30]), [11, 22, 33]);
+/// ```
+pub fn array_init_token<F, T, const N: usize>(mut initializer: F) -> [T; N]
+where
+    F: FnMut(Index<N>) -> T,
+{
+    array_init(|i| initializer(Index(i)))
+}
 
+#[inline]
+/// Initialize an array given its first element and an initializer expression
+/// computing each element from the previous one.
+///
+/// The initializer is given the index of the element and a reference to the
+/// element before it. If `N` is 0, `first` is dropped and the initializer is
+/// never called.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // Successive powers of 3
+/// let powers: [u64; 5] = array_init::array_init_prev(1, |_, prev| prev * 3);
+///
+/// assert_eq!(powers, [1, 3, 9, 27, 81]);
+/// ```
+pub fn array_init_prev<F, T, const N: usize>(first: T, mut initializer: F) -> [T; N]
+where
+    F: FnMut(usize, &T) -> T,
+{
+    // If the `initializer(i, ...)` call panics, `prefix` is dropped along with
+    // the elements it holds, including `first`.
+    let mut prefix = ArrayPrefix::<T, N>::new();
+    if N > 0 {
         // # Safety
         //
-        //   - for D > 0, we are within the array since we start from the
-        //     beginning of the array, and we have `0 <= i < N`.
-        //   - for D < 0, we start at the end of the array and go back one
-        //     place before writing, going back N times in total, finishing
-        //     at the start of the array.
+        //   - `prefix` holds `i < N` elements when pushing the `i`-th one, and
+        //     `i - 1 < prefix.len()` when reading the previous one.
         unsafe {
-            if D < 0 {
-                ptr_i = ptr_i.add(N);
+            prefix.push_unchecked(first);
+            for i in 1..N {
+                let value_i = initializer(i, prefix.as_slice().get_unchecked(i - 1));
+                prefix.push_unchecked(value_i);
             }
-            for i in 0..N {
-                let value_i = initializer(i)?;
-                // We overwrite *ptr_i previously undefined value without reading or dropping it.
-                if D < 0 {
-                    ptr_i = ptr_i.sub(1);
-                }
-                ptr_i.write(value_i);
-                if D > 0 {
-                    ptr_i = ptr_i.add(1);
-                }
+        }
+    }
+    // # Safety
+    //
+    //   - `prefix` holds `N` elements.
+    unsafe { prefix.into_array_unchecked() }
+}
+
+#[inline]
+/// Initialize an array given an initializer expression reading the elements
+/// initialized before.
+///
+/// The initializer is given the index `i` of the element and a slice of the
+/// elements at indices `0 .. i`, which suits dynamic programming. We will always
+/// initialize the elements in order.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // The number of ways to climb `i` stairs, one or two at a time
+/// let ways: [u64; 6] = array_init::array_init_dp(|i, prev| match i {
+///     0 | 1 => 1,
+///     _ => prev[i - 1] + prev[i - 2],
+/// });
+///
+/// assert_eq!(ways, [1, 1, 2, 3, 5, 8]);
+/// ```
+pub fn array_init_dp<F, T, const N: usize>(mut initializer: F) -> [T; N]
+where
+    F: FnMut(usize, &[T]) -> T,
+{
+    // If the `initializer(i, ...)` call panics, `prefix` is dropped along with
+    // the elements it holds.
+    let mut prefix = ArrayPrefix::<T, N>::new();
+    for i in 0..N {
+        let value_i = initializer(i, prefix.as_slice());
+        // # Safety
+        //
+        //   - `prefix` holds `i < N` elements.
+        unsafe { prefix.push_unchecked(value_i) };
+    }
+    // # Safety
+    //
+    //   - `prefix` holds `N` elements.
+    unsafe { prefix.into_array_unchecked() }
+}
+
+#[inline]
+/// Initialize an array with clones of a value.
+///
+/// Unlike `[value; N]`, this only requires `T: Clone`. The value is cloned into
+/// all the elements but the last one, into which it is moved, so that it is
+/// cloned `N - 1` times. If `N` is 0, `value` is dropped.
+///
+/// # Panics
+///
+/// Panics only if `T::clone` panics, in which case the clones made so far and
+/// `value` are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let names: [String; 3] = array_init::array_init_clone(String::from("anonymous"));
+///
+/// assert_eq!(names, ["anonymous", "anonymous", "anonymous"]);
+/// ```
+pub fn array_init_clone<T, const N: usize>(value: T) -> [T; N]
+where
+    T: Clone,
+{
+    // If a `value.clone()` call panics, `prefix` is dropped along with the
+    // clones it holds, and `value` is dropped as well.
+    let mut prefix = ArrayPrefix::<T, N>::new();
+    if N > 0 {
+        // # Safety
+        //
+        //   - `prefix` holds `i < N` elements when pushing the `i`-th one.
+        unsafe {
+            for _ in 1..N {
+                prefix.push_unchecked(value.clone());
+            }
+            prefix.push_unchecked(value);
+        }
+    }
+    // # Safety
+    //
+    //   - `prefix` holds `N` elements.
+    unsafe { prefix.into_array_unchecked() }
+}
+
+#[inline]
+/// Initialize an array with default values.
+///
+/// Unlike `Default` for arrays, this works for any length, and does not
+/// require `T: Copy`, e.g., for arrays of atomics. Since atomics do not need
+/// to be dropped, such arrays are initialized without keeping track of the
+/// initialized elements, see [`uses_fast_path`].
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let counters: [AtomicUsize; 64] = array_init::array_init_default();
+/// counters[42].fetch_add(1, Ordering::Relaxed);
+///
+/// assert_eq!(counters[42].load(Ordering::Relaxed), 1);
+/// ```
+pub fn array_init_default<T, const N: usize>() -> [T; N]
+where
+    T: Default,
+{
+    array_init(|_| T::default())
+}
+
+#[inline]
+/// Initialize an array given an initializer expression returning values to be
+/// converted into the elements.
+///
+/// This is the same as `array_init(|i| initializer(i).into())`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let names: [String; 3] = array_init::array_init_into(|i| ["a", "b", "c"][i]);
+///
+/// assert_eq!(names, ["a", "b", "c"]);
+/// ```
+pub fn array_init_into<F, U, T, const N: usize>(mut initializer: F) -> [T; N]
+where
+    F: FnMut(usize) -> U,
+    U: Into<T>,
+{
+    array_init(|i| initializer(i).into())
+}
+
+#[inline]
+/// Initialize an array given an initializer expression taking an index of
+/// another type than `usize`.
+///
+/// This behaves like [`array_init`], but the index is converted to `I` before
+/// being given to the initializer, which avoids casts in the initializer.
+///
+/// # Panics
+///
+/// Panics if `N - 1`, the largest index, cannot be converted to `I`. The
+/// initializer is not called in that case.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let squares: [u64; 5] = array_init::array_init_indexed(|i: u64| i * i);
+/// assert_eq!(squares, [0, 1, 4, 9, 16]);
+/// ```
+///
+/// ```rust,should_panic
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // 256 does not fit in a `u8`
+/// let bytes: [u8; 257] = array_init::array_init_indexed(|i: u8| i);
+/// ```
+pub fn array_init_indexed<F, I, T, const N: usize>(mut initializer: F) -> [T; N]
+where
+    F: FnMut(I) -> T,
+    I: TryFrom<usize>,
+{
+    assert!(
+        N == 0 || I::try_from(N - 1).is_ok(),
+        "the array length does not fit in the index type"
+    );
+    array_init(|i| match I::try_from(i) {
+        Ok(i) => initializer(i),
+        // `i <= N - 1`, which fits in `I`
+        Err(_) => unreachable!(),
+    })
+}
+
+#[inline]
+/// Initialize a two-dimensional array given an initializer expression.
+///
+/// The initializer is given the row and column index of the element. It is
+/// allowed to mutate external state; we will always initialize the elements in
+/// row-major order, i.e., `(0, 0), (0, 1), ..., (0, N - 1), (1, 0), ...`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // A multiplication table
+/// let table: [[usize; 4]; 3] = array_init::array_init_2d(|i, j| (i + 1) * (j + 1));
+///
+/// assert_eq!(table, [[1, 2, 3, 4], [2, 4, 6, 8], [3, 6, 9, 12]]);
+/// ```
+pub fn array_init_2d<F, T, const M: usize, const N: usize>(mut initializer: F) -> [[T; N]; M]
+where
+    F: FnMut(usize, usize) -> T,
+{
+    // If the initializer panics, the inner call drops the partially filled
+    // row, and the outer call drops the rows that were completed before it.
+    array_init(|i| array_init(|j| initializer(i, j)))
+}
+
+#[inline]
+/// Initialize an array given an iterator
+///
+/// We will iterate until the array is full or the iterator is exhausted. Returns
+/// `None` if the iterator is exhausted before we can fill the array.
+///
+/// The `k`-th element yielded by the iterator is stored at index `k`: the
+/// elements are written in order, from lower to higher indices. See
+/// [`from_iter_reversed`] for the opposite order.
+///
+///   - Once the array is full, extra elements from the iterator (if any)
+///     won't be consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // Initialize an array from an iterator
+/// // producing an array of [1,2,3,4] repeated
+///
+/// let four = [1,2,3,4];
+/// let mut iter = four.iter().copied().cycle();
+/// let arr: [u32; 10] = array_init::from_iter(iter).unwrap();
+/// assert_eq!(arr, [1, 2, 3, 4, 1, 2, 3, 4, 1, 2]);
+/// ```
+pub fn from_iter<Iterable, T, const N: usize>(iterable: Iterable) -> Option<[T; N]>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    try_array_init_impl::<_, _, T, N, 1>({
+        let mut iterator = iterable.into_iter();
+        move |_| iterator.next().ok_or(())
+    })
+    .ok()
+}
+
+#[inline]
+/// Initialize a two-dimensional array given an iterator.
+///
+/// The array is filled in row-major order, i.e., the first `C` elements of the
+/// iterator make the first row, and so on. Returns `None` if the iterator is
+/// exhausted before we can fill the array, dropping the rows completed so far
+/// and the partially filled last one.
+///
+///   - Once the array is full, extra elements from the iterator (if any)
+///     won't be consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let grid: [[u32; 3]; 2] = array_init::from_iter_2d(0..).unwrap();
+///
+/// assert_eq!(grid, [[0, 1, 2], [3, 4, 5]]);
+/// ```
+pub fn from_iter_2d<Iterable, T, const R: usize, const C: usize>(
+    iterable: Iterable,
+) -> Option<[[T; C]; R]>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    let mut iterator = iterable.into_iter();
+    try_array_init_impl::<_, _, [T; C], R, 1>(|_| {
+        try_array_init_impl::<_, _, T, C, 1>(|_| iterator.next().ok_or(()))
+    })
+    .ok()
+}
+
+#[inline]
+/// Initialize an array given an iterator and a mapping expression.
+///
+/// The mapper is given the index of the element and the corresponding element of
+/// the iterator. This is the same as
+/// `from_iter(iterable.into_iter().enumerate().map(...))`: returns `None` if the
+/// iterator is exhausted before we can fill the array.
+///
+///   - Once the array is full, extra elements from the iterator (if any)
+///     won't be consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let words = "a b c".split(' ');
+///
+/// let arr: [String; 3] = array_init::from_iter_indexed(words, |i, word| format!("{}{}", word, i)).unwrap();
+/// assert_eq!(arr, ["a0", "b1", "c2"]);
+/// ```
+pub fn from_iter_indexed<Iterable, M, T, const N: usize>(
+    iterable: Iterable,
+    mut mapper: M,
+) -> Option<[T; N]>
+where
+    Iterable: IntoIterator,
+    M: FnMut(usize, Iterable::Item) -> T,
+{
+    let mut iterator = iterable.into_iter();
+    try_array_init_impl::<_, _, T, N, 1>(|i| iterator.next().map(|item| mapper(i, item)).ok_or(()))
+        .ok()
+}
+
+#[inline]
+/// Initialize an array given an iterator of known length.
+///
+/// This behaves like [`from_iter`], but returns `None` without consuming any
+/// element if the iterator is known to be too short.
+///
+///   - Once the array is full, extra elements from the iterator (if any)
+///     won't be consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let mut iter = [1, 2, 3].iter().copied();
+///
+/// let arr: Option<[u32; 4]> = array_init::from_exact_iter(&mut iter);
+/// assert_eq!(arr, None);
+/// assert_eq!(iter.len(), 3);
+///
+/// let arr: Option<[u32; 2]> = array_init::from_exact_iter(&mut iter);
+/// assert_eq!(arr, Some([1, 2]));
+/// ```
+pub fn from_exact_iter<Iterable, T, const N: usize>(iterable: Iterable) -> Option<[T; N]>
+where
+    Iterable: IntoIterator<Item = T>,
+    Iterable::IntoIter: ExactSizeIterator,
+{
+    let iterator = iterable.into_iter();
+    if iterator.len() < N {
+        return None;
+    }
+    from_iter(iterator)
+}
+
+#[inline]
+/// Initialize an array given a borrowed iterator.
+///
+/// This behaves like [`from_iter`], but leaves the iterator usable afterwards:
+/// if the array could be filled, exactly `N` elements were consumed, so that
+/// successive calls fill successive arrays. Otherwise, the iterator is exhausted.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let mut iter = 0..5;
+///
+/// let first: Option<[u32; 2]> = array_init::fill_from_iter(&mut iter);
+/// let second: Option<[u32; 2]> = array_init::fill_from_iter(&mut iter);
+/// let third: Option<[u32; 2]> = array_init::fill_from_iter(&mut iter);
+///
+/// assert_eq!(first, Some([0, 1]));
+/// assert_eq!(second, Some([2, 3]));
+/// assert_eq!(third, None);
+/// ```
+pub fn fill_from_iter<I, T, const N: usize>(iterator: &mut I) -> Option<[T; N]>
+where
+    I: Iterator<Item = T> + ?Sized,
+{
+    from_iter(iterator)
+}
+
+#[inline]
+/// Initialize an array given a borrowed iterator and a predicate on its
+/// elements.
+///
+/// This behaves like [`fill_from_iter`], but only takes the elements for which
+/// the predicate holds: returns `None` if the iterator is exhausted, or peeks an
+/// element for which the predicate does not hold, before we can fill the array.
+/// That element is left in the iterator, so that it is the next one yielded,
+/// while the elements initialized so far are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let arr: Option<[u32; 3]> = array_init::from_iter_while(&mut (1..).peekable(), |&x| x < 10);
+/// assert_eq!(arr, Some([1, 2, 3]));
+///
+/// let mut iter = [1, 2, -3, 4].iter().copied().peekable();
+///
+/// let arr: Option<[i32; 3]> = array_init::from_iter_while(&mut iter, |&x| x > 0);
+/// assert_eq!(arr, None);
+/// assert_eq!(iter.next(), Some(-3));
+/// ```
+pub fn from_iter_while<I, P, T, const N: usize>(
+    iterator: &mut Peekable<I>,
+    mut predicate: P,
+) -> Option<[T; N]>
+where
+    I: Iterator<Item = T>,
+    P: FnMut(&T) -> bool,
+{
+    try_array_init_impl::<_, _, T, N, 1>(|_| iterator.next_if(&mut predicate).ok_or(())).ok()
+}
+
+#[inline]
+/// Initialize an array given an iterator, handing the iterator back if it runs
+/// short.
+///
+/// This behaves like [`from_iter`], but if the iterator is exhausted before the
+/// array is full, returns the number of elements that were consumed together
+/// with the (exhausted) iterator. The consumed elements are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let arr: Result<[u32; 3], _> = array_init::from_iter_remainder(1..=4);
+/// assert_eq!(arr.ok(), Some([1, 2, 3]));
+///
+/// let (consumed, mut rest) = array_init::from_iter_remainder::<_, u32, 5>(1..=4).unwrap_err();
+/// assert_eq!(consumed, 4);
+/// assert_eq!(rest.next(), None);
+/// ```
+pub fn from_iter_remainder<Iterable, T, const N: usize>(
+    iterable: Iterable,
+) -> Result<[T; N], (usize, Iterable::IntoIter)>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    let mut iterator = iterable.into_iter();
+    try_array_init_impl::<_, _, T, N, 1>(|i| iterator.next().ok_or(i))
+        .map_err(|consumed| (consumed, iterator))
+}
+
+#[inline]
+/// Initialize an array given an iterator, also returning the iterator.
+///
+/// This behaves like [`from_iter`], but once the array is full, the iterator is
+/// returned along with it, positioned after the `N` consumed elements, so that
+/// the following elements can still be processed. See [`fill_from_iter`] to
+/// borrow the iterator instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let (header, body) = array_init::from_iter_with_rest::<_, u8, 2>(b"\x01\x02payload".iter().copied()).unwrap();
+///
+/// assert_eq!(header, [1, 2]);
+/// assert_eq!(body.collect::<Vec<_>>(), b"payload");
+/// ```
+pub fn from_iter_with_rest<Iterable, T, const N: usize>(
+    iterable: Iterable,
+) -> Option<([T; N], Iterable::IntoIter)>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    let mut iterator = iterable.into_iter();
+    let array = fill_from_iter(&mut iterator)?;
+    Some((array, iterator))
+}
+
+#[inline]
+/// Initialize an array given an iterator, also returning the number of elements
+/// that were consumed.
+///
+/// This behaves like [`from_iter`]: the count is `N` if the array could be
+/// filled, and the number of elements the iterator yielded otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let mut iter = 0..10;
+/// let mut progress = 0;
+/// loop {
+///     let (chunk, count): (Option<[u32; 4]>, _) = array_init::from_iter_counted(&mut iter);
+///     progress += count;
+///     if chunk.is_none() {
+///         break;
+///     }
+/// }
+/// assert_eq!(progress, 10);
+/// ```
+pub fn from_iter_counted<Iterable, T, const N: usize>(iterable: Iterable) -> (Option<[T; N]>, usize)
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    match from_iter_remainder(iterable) {
+        Ok(array) => (Some(array), N),
+        Err((consumed, _)) => (None, consumed),
+    }
+}
+
+#[inline]
+/// Initialize an array given an iterator, filling the remaining elements with
+/// their default value if the iterator runs short.
+///
+/// See [`from_iter_padded`] to also know where the padding starts.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let arr: [u32; 5] = array_init::from_iter_or_default(1..4);
+///
+/// assert_eq!(arr, [1, 2, 3, 0, 0]);
+/// ```
+pub fn from_iter_or_default<Iterable, T, const N: usize>(iterable: Iterable) -> [T; N]
+where
+    Iterable: IntoIterator<Item = T>,
+    T: Default,
+{
+    from_iter_padded(iterable).0
+}
+
+#[inline]
+/// Initialize an array given an iterator, filling the remaining elements with
+/// their default value if the iterator runs short, and returning the number of
+/// elements taken from the iterator.
+///
+/// The elements from that index on are the padding. Once the iterator is
+/// exhausted, it is not polled again.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let (arr, len): ([u32; 5], _) = array_init::from_iter_padded(1..4);
+///
+/// assert_eq!(arr, [1, 2, 3, 0, 0]);
+/// assert_eq!(len, 3);
+/// ```
+pub fn from_iter_padded<Iterable, T, const N: usize>(iterable: Iterable) -> ([T; N], usize)
+where
+    Iterable: IntoIterator<Item = T>,
+    T: Default,
+{
+    let mut iterator = iterable.into_iter().fuse();
+    let mut consumed = 0;
+    let array = array_init(|_| match iterator.next() {
+        Some(value) => {
+            consumed += 1;
+            value
+        }
+        None => T::default(),
+    });
+    (array, consumed)
+}
+
+#[inline]
+/// Initialize the first `K` elements of an array given an iterator, and the
+/// others with their default value.
+///
+/// Exactly `K` elements are taken from the iterator, and `None` is returned if
+/// it runs short, dropping the elements taken so far. Unlike
+/// [`from_iter_or_default`], the elements from index `K` on are always the
+/// default value, even if the iterator has more elements.
+///
+/// # Panics
+///
+/// Panics if `K` is greater than `N`. The lengths being known at compile time,
+/// this check is optimized away otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let record: Option<[u32; 5]> = array_init::from_iter_prefix::<_, _, 2, 5>(1..);
+/// assert_eq!(record, Some([1, 2, 0, 0, 0]));
+///
+/// let record: Option<[u32; 5]> = array_init::from_iter_prefix::<_, _, 2, 5>(1..2);
+/// assert_eq!(record, None);
+/// ```
+pub fn from_iter_prefix<Iterable, T, const K: usize, const N: usize>(
+    iterable: Iterable,
+) -> Option<[T; N]>
+where
+    Iterable: IntoIterator<Item = T>,
+    T: Default,
+{
+    assert!(K <= N, "the prefix must not be longer than the array");
+    let mut iterator = iterable.into_iter();
+    try_array_init_impl::<_, _, T, N, 1>(|i| {
+        if i < K {
+            iterator.next().ok_or(())
+        } else {
+            Ok(T::default())
+        }
+    })
+    .ok()
+}
+
+#[inline]
+/// Initialize an array given an iterator yielding exactly as many elements.
+///
+/// Unlike [`from_iter`], which ignores any extra elements, this checks that the
+/// iterator is exhausted once the array is full, by pulling one more element
+/// from it. Returns [`ArrayInitError::TooShort`] or [`ArrayInitError::TooLong`]
+/// otherwise, dropping the elements consumed so far.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::ArrayInitError;
+///
+/// let arr: Result<[u32; 3], _> = array_init::from_iter_exact(0..3);
+/// assert_eq!(arr, Ok([0, 1, 2]));
+///
+/// let arr: Result<[u32; 3], _> = array_init::from_iter_exact(0..2);
+/// assert_eq!(arr, Err(ArrayInitError::TooShort { got: 2, expected: 3 }));
+///
+/// let arr: Result<[u32; 3], _> = array_init::from_iter_exact(0..4);
+/// assert_eq!(arr, Err(ArrayInitError::TooLong { expected: 3 }));
+/// ```
+pub fn from_iter_exact<Iterable, T, const N: usize>(
+    iterable: Iterable,
+) -> Result<[T; N], ArrayInitError>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    let mut iterator = iterable.into_iter();
+    let array = try_array_init_impl::<_, _, T, N, 1>(|i| {
+        iterator.next().ok_or(ArrayInitError::TooShort {
+            got: i,
+            expected: N,
+        })
+    })?;
+    match iterator.next() {
+        None => Ok(array),
+        Some(_) => Err(ArrayInitError::TooLong { expected: N }),
+    }
+}
+
+#[inline]
+/// Initialize an array given an iterator yielding exactly as many elements,
+/// checked in debug builds only.
+///
+/// This behaves like [`from_iter`], but with debug assertions enabled, one more
+/// element is pulled from the iterator once the array is full, to check that it
+/// is exhausted, as [`from_iter_exact`] does. Without debug assertions, the
+/// iterator is left as is, so that the check costs nothing in release builds.
+///
+/// # Panics
+///
+/// Panics if debug assertions are enabled and the iterator yields more than `N`
+/// elements.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let arr: Option<[u32; 3]> = array_init::from_iter_debug_checked(0..3);
+/// assert_eq!(arr, Some([0, 1, 2]));
+///
+/// let arr: Option<[u32; 3]> = array_init::from_iter_debug_checked(0..2);
+/// assert_eq!(arr, None);
+/// ```
+pub fn from_iter_debug_checked<Iterable, T, const N: usize>(iterable: Iterable) -> Option<[T; N]>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    let mut iterator = iterable.into_iter();
+    let array = fill_from_iter(&mut iterator)?;
+    debug_assert!(
+        iterator.next().is_none(),
+        "the iterator yielded more than {} elements",
+        N
+    );
+    Some(array)
+}
+
+#[inline]
+/// Initialize an array given an iterator of fallible values.
+///
+/// We will iterate until the array is full, the iterator yields an `Err`, or the
+/// iterator is exhausted. The first `Err` is returned as
+/// [`ArrayInitError::Closure`], and an iterator that is exhausted before we can
+/// fill the array results in [`ArrayInitError::TooShort`]. In both cases, the
+/// elements initialized so far are dropped.
+///
+///   - Once the array is full, extra elements from the iterator (if any)
+///     won't be consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::ArrayInitError;
+///
+/// let parsed: Result<[u8; 3], _> = array_init::try_from_iter("1 2 3".split(' ').map(str::parse));
+/// assert_eq!(parsed, Ok([1, 2, 3]));
+///
+/// let parsed: Result<[u8; 3], _> = array_init::try_from_iter("1 x 3".split(' ').map(str::parse));
+/// assert!(matches!(parsed, Err(ArrayInitError::Closure(_))));
+///
+/// let parsed: Result<[u8; 3], _> = array_init::try_from_iter("1 2".split(' ').map(str::parse));
+/// assert!(matches!(parsed, Err(ArrayInitError::TooShort { got: 2, .. })));
+/// ```
+pub fn try_from_iter<Iterable, T, Err, const N: usize>(
+    iterable: Iterable,
+) -> Result<[T; N], ArrayInitError<Err>>
+where
+    Iterable: IntoIterator<Item = Result<T, Err>>,
+{
+    let mut iterator = iterable.into_iter();
+    try_array_init_impl::<_, _, T, N, 1>(|i| match iterator.next() {
+        Some(Ok(value)) => Ok(value),
+        Some(Err(err)) => Err(ArrayInitError::Closure(err)),
+        None => Err(ArrayInitError::TooShort {
+            got: i,
+            expected: N,
+        }),
+    })
+}
+
+#[inline]
+/// Initialize an array given an iterator yielding exactly as many fallible
+/// values.
+///
+/// This is the strict version of [`try_from_iter`]: once the array is full, one
+/// more element is pulled from the iterator, and
+/// [`ArrayInitError::TooLong`] is returned if there is one, whether it is an
+/// `Ok` or an `Err`. The elements initialized so far are dropped on any error.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::ArrayInitError;
+///
+/// let parsed: Result<[u8; 3], _> =
+///     array_init::try_from_iter_exact("1 2 3".split(' ').map(str::parse));
+/// assert_eq!(parsed, Ok([1, 2, 3]));
+///
+/// let parsed: Result<[u8; 3], _> =
+///     array_init::try_from_iter_exact("1 2 3 4".split(' ').map(str::parse));
+/// assert!(matches!(parsed, Err(ArrayInitError::TooLong { .. })));
+/// ```
+pub fn try_from_iter_exact<Iterable, T, Err, const N: usize>(
+    iterable: Iterable,
+) -> Result<[T; N], ArrayInitError<Err>>
+where
+    Iterable: IntoIterator<Item = Result<T, Err>>,
+{
+    let mut iterator = iterable.into_iter();
+    let array = try_from_iter(&mut iterator)?;
+    match iterator.next() {
+        None => Ok(array),
+        Some(_) => Err(ArrayInitError::TooLong { expected: N }),
+    }
+}
+
+#[inline]
+/// Initialize an array of pairs given two iterators.
+///
+/// This is the same as `from_iter(first.into_iter().zip(second))`: returns `None`
+/// if either iterator is exhausted before we can fill the array. See
+/// [`from_iters_zip_checked`] to know which one was.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let names = ["x", "y", "z"];
+/// let values = [1, 2, 3];
+/// let arr: [(&str, u32); 3] = array_init::from_iters_zip(names.iter().copied(), values.iter().copied()).unwrap();
+///
+/// assert_eq!(arr, [("x", 1), ("y", 2), ("z", 3)]);
+/// ```
+pub fn from_iters_zip<First, Second, A, B, const N: usize>(
+    first: First,
+    second: Second,
+) -> Option<[(A, B); N]>
+where
+    First: IntoIterator<Item = A>,
+    Second: IntoIterator<Item = B>,
+{
+    from_iters_zip_checked(first, second).ok()
+}
+
+#[inline]
+/// Initialize an array of pairs given two iterators, reporting which one runs
+/// short.
+///
+/// For each element, we first pull from `first`, then from `second`. If one of
+/// them is exhausted before we can fill the array, the returned [`ZipError`]
+/// tells which one, and how many pairs were built before.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::ZipError;
+///
+/// let arr: Result<[(u32, u32); 3], _> = array_init::from_iters_zip_checked(0..3, 0..2);
+///
+/// assert_eq!(arr, Err(ZipError::SecondTooShort { got: 2 }));
+/// ```
+pub fn from_iters_zip_checked<First, Second, A, B, const N: usize>(
+    first: First,
+    second: Second,
+) -> Result<[(A, B); N], ZipError>
+where
+    First: IntoIterator<Item = A>,
+    Second: IntoIterator<Item = B>,
+{
+    let mut first = first.into_iter();
+    let mut second = second.into_iter();
+    try_array_init_impl::<_, _, (A, B), N, 1>(|i| {
+        let a = first.next().ok_or(ZipError::FirstTooShort { got: i })?;
+        let b = second.next().ok_or(ZipError::SecondTooShort { got: i })?;
+        Ok((a, b))
+    })
+}
+
+#[inline]
+/// Initialize an array in reverse given an iterator
+///
+/// We will iterate until the array is full or the iterator is exhausted. Returns
+/// `None` if the iterator is exhausted before we can fill the array.
+///
+///   - Once the array is full, extra elements from the iterator (if any)
+///     won't be consumed.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // Initialize an array from an iterator
+/// // producing an array of [4,3,2,1] repeated, finishing with 1.
+///
+/// let four = [1,2,3,4];
+/// let mut iter = four.iter().copied().cycle();
+/// let arr: [u32; 10] = array_init::from_iter_reversed(iter).unwrap();
+/// assert_eq!(arr, [2, 1, 4, 3, 2, 1, 4, 3, 2, 1]);
+/// ```
+pub fn from_iter_reversed<Iterable, T, const N: usize>(iterable: Iterable) -> Option<[T; N]>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    try_array_init_impl::<_, _, T, N, -1>({
+        let mut iterator = iterable.into_iter();
+        move |_| iterator.next().ok_or(())
+    })
+    .ok()
+}
+
+#[inline]
+/// Initialize an array given an initializer expression that may fail.
+///
+/// The initializer is given the index (between 0 and `N - 1` included) of the element, and returns a `Result<T, Err>,`. It is allowed
+/// to mutate external state; we will always initialize from lower to higher indices.
+///
+/// For types that do not need to be dropped, such as `Copy` types, the elements
+/// initialized before an `Err` are simply forgotten, without keeping track of
+/// them (see [`uses_fast_path`]), so there is no need for a separate `Copy`
+/// version of this function.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// #[derive(PartialEq,Eq,Debug)]
+/// struct DivideByZero;
+///
+/// fn inv(i : usize) -> Result<f64,DivideByZero> {
+///     if i == 0 {
+///         Err(DivideByZero)
+///     } else {
+///         Ok(1./(i as f64))
+///     }
+/// }
+///
+/// // If the initializer does not fail, we get an initialized array
+/// let arr: [f64; 3] = array_init::try_array_init(|i| inv(3-i)).unwrap();
+/// assert_eq!(arr,[1./3., 1./2., 1./1.]);
+///
+/// // The initializer fails
+/// let res : Result<[f64;4], DivideByZero> = array_init::try_array_init(|i| inv(3-i));
+/// assert_eq!(res,Err(DivideByZero));
+/// ```
+pub fn try_array_init<Err, F, T, const N: usize>(initializer: F) -> Result<[T; N], Err>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    try_array_init_impl::<Err, F, T, N, 1>(initializer)
+}
+
+#[inline]
+/// Initialize an array given an initializer expression that may fail.
+///
+/// This is the same as [`try_array_init`], named after the unstable
+/// `core::array::try_from_fn`, so that switching between the two is a matter of
+/// changing the path once it is stable. Unlike the standard library version,
+/// which accepts any `Try` type, the initializer must return a `Result`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use std::convert::TryFrom;
+///
+/// let arr: Result<[u8; 5], _> = array_init::try_from_fn(|i| u8::try_from(i * 100));
+/// assert!(arr.is_err());
+///
+/// let arr: Result<[u8; 3], _> = array_init::try_from_fn(|i| u8::try_from(i * 100));
+/// assert_eq!(arr, Ok([0, 100, 200]));
+/// ```
+pub fn try_from_fn<F, T, Err, const N: usize>(initializer: F) -> Result<[T; N], Err>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    try_array_init(initializer)
+}
+
+#[inline]
+/// Initialize an array given an initializer expression that may fail, from the
+/// last element to the first.
+///
+/// This behaves like [`try_array_init`], but we will always initialize from
+/// higher to lower indices. If the initializer fails for index `i`, the
+/// elements at indices `i + 1 .. N` are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // Each element must be smaller than the one after it
+/// let values = [1, 5, 3, 4];
+/// let mut next = u32::MAX;
+/// let res: Result<[u32; 4], usize> = array_init::try_array_init_rev(|i| {
+///     if values[i] < next {
+///         next = values[i];
+///         Ok(values[i])
+///     } else {
+///         Err(i)
+///     }
+/// });
+/// assert_eq!(res, Err(1));
+/// ```
+pub fn try_array_init_rev<Err, F, T, const N: usize>(mut initializer: F) -> Result<[T; N], Err>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    try_array_init_impl::<Err, _, T, N, -1>(move |i| initializer(N - 1 - i))
+}
+
+#[inline]
+/// Initialize an array given an initializer expression that may not produce a
+/// value.
+///
+/// The initializer is given the index of the element. Returns `Some` only if
+/// all `N` calls returned `Some`: the first `None` stops the initialization,
+/// dropping the elements initialized so far. It is allowed to mutate external
+/// state; we will always initialize from lower to higher indices.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let values = [4, 8, 15, 16, 23, 42];
+///
+/// let arr: Option<[u32; 4]> = array_init::opt_array_init(|i| values.get(i).copied());
+/// assert_eq!(arr, Some([4, 8, 15, 16]));
+///
+/// let arr: Option<[u32; 8]> = array_init::opt_array_init(|i| values.get(i).copied());
+/// assert_eq!(arr, None);
+/// ```
+pub fn opt_array_init<F, T, const N: usize>(mut initializer: F) -> Option<[T; N]>
+where
+    F: FnMut(usize) -> Option<T>,
+{
+    try_array_init_impl::<_, _, T, N, 1>(|i| initializer(i).ok_or(())).ok()
+}
+
+#[inline]
+/// Initialize an array given an initializer expression that may skip source
+/// indices.
+///
+/// The initializer is called with increasing source indices, starting from 0,
+/// and the values it returns as `Some` are stored in order, while `None` skips
+/// the source index. This is the same as
+/// `from_iter((0..max_attempts).filter_map(initializer))`.
+///
+/// Returns `None` if the initializer returned fewer than `N` values once it was
+/// called with all the source indices below `max_attempts`, in which case the
+/// values returned so far are dropped. Once the array is full, the initializer
+/// is not called anymore.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// fn odd(i: usize) -> Option<usize> {
+///     if i % 2 == 1 {
+///         Some(i)
+///     } else {
+///         None
+///     }
+/// }
+///
+/// let arr: Option<[usize; 3]> = array_init::array_init_filter_map(10, odd);
+/// assert_eq!(arr, Some([1, 3, 5]));
+///
+/// let arr: Option<[usize; 3]> = array_init::array_init_filter_map(5, odd);
+/// assert_eq!(arr, None);
+/// ```
+pub fn array_init_filter_map<F, T, const N: usize>(
+    max_attempts: usize,
+    initializer: F,
+) -> Option<[T; N]>
+where
+    F: FnMut(usize) -> Option<T>,
+{
+    from_iter((0..max_attempts).filter_map(initializer))
+}
+
+#[inline]
+/// Initialize an array given an initializer expression that may fail, reporting
+/// the index at which it failed.
+///
+/// This behaves like [`try_array_init`], but on failure the error is returned
+/// together with the index of the element whose initializer failed. The elements
+/// initialized before that index are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let res: Result<[u32; 4], (usize, &str)> = array_init::try_array_init_partial(|i| {
+///     if i < 2 {
+///         Ok(i as u32)
+///     } else {
+///         Err("too big")
+///     }
+/// });
+/// assert_eq!(res, Err((2, "too big")));
+/// ```
+pub fn try_array_init_partial<Err, F, T, const N: usize>(
+    mut initializer: F,
+) -> Result<[T; N], (usize, Err)>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    try_array_init_impl::<_, _, T, N, 1>(|i| initializer(i).map_err(|err| (i, err)))
+}
+
+#[inline]
+/// Initialize an array given an initializer expression that may fail, handing
+/// back the elements initialized before the failure.
+///
+/// This behaves like [`try_array_init`], but on failure the error is returned
+/// together with an [`ArrayPrefix`] owning the elements initialized so far,
+/// instead of dropping them. The length of the prefix is the index at which the
+/// initializer failed.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let res: Result<[String; 4], _> = array_init::try_array_init_recover(|i| {
+///     if i < 2 {
+///         Ok(i.to_string())
+///     } else {
+///         Err("too big")
+///     }
+/// });
+///
+/// let (err, prefix) = res.unwrap_err();
+/// assert_eq!(err, "too big");
+/// assert_eq!(prefix.as_slice(), ["0", "1"]);
+/// ```
+pub fn try_array_init_recover<Err, F, T, const N: usize>(
+    mut initializer: F,
+) -> Result<[T; N], (Err, ArrayPrefix<T, N>)>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    // If the `initializer(i)` call panics, `prefix` is dropped along with the
+    // elements it holds.
+    let mut prefix = ArrayPrefix::new();
+    for i in 0..N {
+        match initializer(i) {
+            // # Safety
+            //
+            //   - `prefix` holds `i < N` elements.
+            Ok(value_i) => unsafe { prefix.push_unchecked(value_i) },
+            Err(err) => return Err((err, prefix)),
+        }
+    }
+    // # Safety
+    //
+    //   - `prefix` holds `N` elements.
+    Ok(unsafe { prefix.into_array_unchecked() })
+}
+
+#[inline]
+/// Initialize the first elements of an array given an initializer expression
+/// that may stop early.
+///
+/// The initializer is called with increasing indices until it returns `None`,
+/// or the array is full. The elements initialized so far are returned as an
+/// [`ArrayPrefix`], which drops them along with itself.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let words = "one two three";
+/// let mut split = words.split(' ');
+///
+/// let prefix = array_init::array_init_until::<_, &str, 8>(|_| split.next());
+/// assert_eq!(prefix.len(), 3);
+///
+/// let words: Vec<&str> = prefix.into_iter().collect();
+/// assert_eq!(words, ["one", "two", "three"]);
+/// ```
+pub fn array_init_until<F, T, const N: usize>(mut initializer: F) -> ArrayPrefix<T, N>
+where
+    F: FnMut(usize) -> Option<T>,
+{
+    // If the `initializer(i)` call panics, `prefix` is dropped along with the
+    // elements it holds.
+    let mut prefix = ArrayPrefix::new();
+    for i in 0..N {
+        match initializer(i) {
+            // # Safety
+            //
+            //   - `prefix` holds `i < N` elements.
+            Some(value_i) => unsafe { prefix.push_unchecked(value_i) },
+            None => break,
+        }
+    }
+    prefix
+}
+
+#[inline]
+/// Initialize an array given a source array and a mapping expression. The size of the source array
+/// is the same as the size of the returned array.
+///
+/// The mapper is given an element from the source array and maps it to an element in the
+/// destination.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // Initialize an array of length 50 containing successive squares
+/// let arr: [usize; 50] = array_init::array_init(|i| i * i);
+///
+/// // Map each usize element to a u64 element.
+/// let u64_arr: [u64; 50] = array_init::map_array_init(&arr, |element| *element as u64);
+///
+/// assert!(u64_arr.iter().enumerate().all(|(i, &x)| x == (i * i) as u64));
+/// ```
+pub fn map_array_init<M, T, U, const N: usize>(source: &[U; N], mut mapper: M) -> [T; N]
+where
+    M: FnMut(&U) -> T,
+{
+    // # Safety
+    //   - The array size is known at compile time so we are certain that both the source and
+    //     desitination have the same size. If the two arrays are of the same size we know that a
+    //     valid index for one would be a valid index for the other.
+    array_init(|index| unsafe { mapper(source.get_unchecked(index)) })
+}
+
+#[inline]
+/// Initialize an array given a source array, consumed by value, and a mapping
+/// expression. The size of the source array is the same as the size of the
+/// returned array.
+///
+/// The mapper is given the index of the element and the element of the source
+/// array at that index, and maps it to the element of the destination at that
+/// index. We will always map the elements in order.
+///
+/// If the mapper panics, both the elements of the destination produced so far
+/// and the elements of the source that were not mapped yet are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let names = [String::from("a"), String::from("b"), String::from("c")];
+///
+/// let numbered: [String; 3] = array_init::map_array(names, |i, mut name| {
+///     name.push_str(&i.to_string());
+///     name
+/// });
+///
+/// assert_eq!(numbered, ["a0", "b1", "c2"]);
+/// ```
+pub fn map_array<M, T, U, const N: usize>(source: [U; N], mut mapper: M) -> [T; N]
+where
+    M: FnMut(usize, U) -> T,
+{
+    let mut source = IntoIter::new(source);
+    array_init(|index| match source.next() {
+        Some(element) => mapper(index, element),
+        // # Safety
+        //
+        //   - `array_init` calls us exactly `N` times, and each call consumes
+        //     a single one of the `N` elements of `source`.
+        None => unsafe { hint::unreachable_unchecked() },
+    })
+}
+
+#[inline]
+/// Initialize an array given a source array, consumed by value, and a mapping
+/// expression that may fail.
+///
+/// This behaves like [`map_array`], but the first `Err` returned by the mapper
+/// stops the initialization. Both the elements of the destination produced so
+/// far and the elements of the source that were not mapped yet are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let strings = ["1", "2", "3"];
+///
+/// let numbers: Result<[u8; 3], _> = array_init::try_map_array(strings, |_, s| s.parse());
+/// assert_eq!(numbers, Ok([1, 2, 3]));
+///
+/// let strings = ["1", "x", "3"];
+///
+/// let numbers: Result<[u8; 3], _> = array_init::try_map_array(strings, |_, s| s.parse());
+/// assert!(numbers.is_err());
+/// ```
+pub fn try_map_array<Err, M, T, U, const N: usize>(
+    source: [U; N],
+    mut mapper: M,
+) -> Result<[T; N], Err>
+where
+    M: FnMut(usize, U) -> Result<T, Err>,
+{
+    let mut source = IntoIter::new(source);
+    try_array_init(|index| match source.next() {
+        Some(element) => mapper(index, element),
+        // # Safety
+        //
+        //   - `try_array_init` calls us at most `N` times, and each call
+        //     consumes a single one of the `N` elements of `source`.
+        None => unsafe { hint::unreachable_unchecked() },
+    })
+}
+
+#[inline]
+/// Initialize an array given a source array, consumed by value, and a mapping
+/// expression that may fail, handing back the elements on failure.
+///
+/// This behaves like [`try_map_array`], but on the first `Err`, the elements of
+/// the destination produced so far and the elements of the source that were
+/// not mapped yet are returned along with the error, instead of being dropped.
+/// The source element given to the failing call is not among them.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let strings = ["1", "2", "x", "4"];
+///
+/// let numbers: Result<[u8; 4], _> = array_init::try_map_array_recover(strings, |_, s| s.parse());
+///
+/// let (_, mapped, rest) = numbers.unwrap_err();
+/// assert_eq!(mapped.as_slice(), [1, 2]);
+/// assert_eq!(rest.as_slice(), ["4"]);
+/// ```
+pub fn try_map_array_recover<Err, M, T, U, const N: usize>(
+    source: [U; N],
+    mut mapper: M,
+) -> Result<[T; N], (Err, ArrayPrefix<T, N>, IntoIter<U, N>)>
+where
+    M: FnMut(usize, U) -> Result<T, Err>,
+{
+    let mut source = IntoIter::new(source);
+    try_array_init_recover(|index| match source.next() {
+        Some(element) => mapper(index, element),
+        // # Safety
+        //
+        //   - `try_array_init_recover` calls us at most `N` times, and each
+        //     call consumes a single one of the `N` elements of `source`.
+        None => unsafe { hint::unreachable_unchecked() },
+    })
+    .map_err(|(err, prefix)| (err, prefix, source))
+}
+
+#[inline]
+/// Initialize an array given two source arrays, consumed by value, and an
+/// expression combining their elements.
+///
+/// The combining expression is given the elements at the same index in `first`
+/// and `second`. We will always combine the elements in order. If it panics,
+/// the elements produced so far and the source elements that were not combined
+/// yet are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let sums = array_init::zip_with([1, 2, 3], [10, 20, 30], |a, b| a + b);
+///
+/// assert_eq!(sums, [11, 22, 33]);
+/// ```
+pub fn zip_with<F, A, B, C, const N: usize>(first: [A; N], second: [B; N], mut f: F) -> [C; N]
+where
+    F: FnMut(A, B) -> C,
+{
+    let mut second = IntoIter::new(second);
+    map_array(first, |_, a| match second.next() {
+        Some(b) => f(a, b),
+        // # Safety
+        //
+        //   - `map_array` calls us exactly `N` times, and each call consumes a
+        //     single one of the `N` elements of `second`.
+        None => unsafe { hint::unreachable_unchecked() },
+    })
+}
+
+#[inline]
+/// Split an array of pairs, consumed by value, into an array of the first
+/// elements and an array of the second elements.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let pairs = [("a", 1), ("b", 2), ("c", 3)];
+///
+/// let (names, values) = array_init::unzip_array(pairs);
+/// assert_eq!(names, ["a", "b", "c"]);
+/// assert_eq!(values, [1, 2, 3]);
+/// ```
+pub fn unzip_array<A, B, const N: usize>(source: [(A, B); N]) -> ([A; N], [B; N]) {
+    let mut seconds = ArrayPrefix::<B, N>::new();
+    let firsts = map_array(source, |_, (a, b)| {
+        // # Safety
+        //
+        //   - `map_array` calls us exactly `N` times.
+        unsafe { seconds.push_unchecked(b) };
+        a
+    });
+    // # Safety
+    //
+    //   - `seconds` holds `N` elements.
+    (firsts, unsafe { seconds.into_array_unchecked() })
+}
+
+#[inline]
+/// Fold an array, consumed by value, into a single value.
+///
+/// The folding expression is given the accumulated value and the elements of
+/// the array, in order from lower to higher indices. If it panics, the elements
+/// that were not folded yet are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let words = [String::from("a"), String::from("b"), String::from("c")];
+///
+/// let sentence = array_init::array_fold(words, String::new(), |mut acc, word| {
+///     acc.push_str(&word);
+///     acc
+/// });
+/// assert_eq!(sentence, "abc");
+/// ```
+pub fn array_fold<F, T, B, const N: usize>(array: [T; N], init: B, f: F) -> B
+where
+    F: FnMut(B, T) -> B,
+{
+    IntoIter::new(array).fold(init, f)
+}
+
+#[inline]
+/// Initialize two arrays given an initializer expression producing pairs.
+///
+/// This is the same as `unzip_array(array_init(initializer))`, without building
+/// the intermediate array of pairs. If the initializer panics, the elements of
+/// both arrays initialized so far are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let (values, keys): ([u32; 3], [String; 3]) =
+///     array_init::array_init_with_aux(|i| ((i * i) as u32, format!("key{}", i)));
+///
+/// assert_eq!(values, [0, 1, 4]);
+/// assert_eq!(keys, ["key0", "key1", "key2"]);
+/// ```
+pub fn array_init_with_aux<F, T, A, const N: usize>(mut initializer: F) -> ([T; N], [A; N])
+where
+    F: FnMut(usize) -> (T, A),
+{
+    // If the `initializer(i)` call panics, `aux` is dropped along with the
+    // elements it holds, and `array_init` drops the other elements.
+    let mut aux = ArrayPrefix::<A, N>::new();
+    let array = array_init(|i| {
+        let (value, aux_value) = initializer(i);
+        // # Safety
+        //
+        //   - `array_init` calls us exactly `N` times.
+        unsafe { aux.push_unchecked(aux_value) };
+        value
+    });
+    // # Safety
+    //
+    //   - `aux` holds `N` elements.
+    (array, unsafe { aux.into_array_unchecked() })
+}
+
+#[inline]
+/// Initialize an array given a source array and an expression computing each
+/// element from a window of `K` consecutive elements of the source.
+///
+/// The initializer is given the windows in order, the `i`-th one starting at
+/// index `i` of the source. Stable Rust cannot express the length of the result
+/// as `N - K + 1`, so it is a separate parameter, usually inferred from the type
+/// of the result.
+///
+/// # Panics
+///
+/// Panics if `K` is 0, or if `M` is not `N - K + 1`. The lengths being known at
+/// compile time, this check is optimized away otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let samples = [1.0, 3.0, 2.0, 6.0, 4.0];
+///
+/// let averages: [f64; 4] = array_init::array_init_windows(&samples, |&[a, b]| (a + b) / 2.0);
+/// assert_eq!(averages, [2.0, 2.5, 4.0, 5.0]);
+/// ```
+pub fn array_init_windows<F, T, W, const N: usize, const K: usize, const M: usize>(
+    source: &[T; N],
+    mut initializer: F,
+) -> [W; M]
+where
+    F: FnMut(&[T; K]) -> W,
+{
+    assert!(
+        K > 0 && M + K == N + 1,
+        "there must be one window of length `K` per element of the result"
+    );
+    array_init(|i| {
+        // # Safety
+        //
+        //   - `i < M`, so `i + K <= N`, and `source[i .. i + K]` is a `[T; K]`.
+        let window = unsafe { &*(source.as_ptr().add(i) as *const [T; K]) };
+        initializer(window)
+    })
+}
+
+#[inline]
+/// Split an array, consumed by value, into an array of its first `M` elements
+/// and an array of the remaining `R` elements.
+///
+/// Stable Rust cannot express the length of the second array as `N - M`, so it
+/// is a separate parameter, usually inferred from the type of the result.
+///
+/// # Panics
+///
+/// Panics if `M + R` is not `N`, in which case `array` is dropped. The lengths
+/// being known at compile time, this check is optimized away otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let numbers: [String; 5] = array_init::array_init(|i| i.to_string());
+///
+/// let (head, tail): ([_; 2], [_; 3]) = array_init::split_array(numbers);
+/// assert_eq!(head, ["0", "1"]);
+/// assert_eq!(tail, ["2", "3", "4"]);
+/// ```
+pub fn split_array<T, const N: usize, const M: usize, const R: usize>(
+    array: [T; N],
+) -> ([T; M], [T; R]) {
+    assert!(
+        M + R == N,
+        "the lengths of the halves must add up to the length"
+    );
+    let array = mem::ManuallyDrop::new(array);
+    let ptr = array.as_ptr();
+    // # Safety
+    //
+    //   - `M + R == N`, so `array[.. M]` and `array[M ..]` are `[T; M]` and
+    //     `[T; R]`, which are read once each and never dropped through `array`.
+    unsafe {
+        (
+            (ptr as *const [T; M]).read(),
+            (ptr.add(M) as *const [T; R]).read(),
+        )
+    }
+}
+
+#[inline]
+/// Concatenate two arrays, consumed by value, into one.
+///
+/// This is the inverse of [`split_array`]. Stable Rust cannot express the
+/// length of the result as `A + B`, so it is a separate parameter, usually
+/// inferred from the type of the result.
+///
+/// # Panics
+///
+/// Panics if `A + B` is not `N`, in which case `first` and `second` are dropped.
+/// The lengths being known at compile time, this check is optimized away
+/// otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let head: [String; 2] = array_init::array_init(|i| i.to_string());
+/// let tail: [String; 1] = [String::from("2")];
+///
+/// let numbers: [_; 3] = array_init::concat_arrays(head, tail);
+/// assert_eq!(numbers, ["0", "1", "2"]);
+/// ```
+pub fn concat_arrays<T, const A: usize, const B: usize, const N: usize>(
+    first: [T; A],
+    second: [T; B],
+) -> [T; N] {
+    assert!(
+        A + B == N,
+        "the lengths of the arrays must add up to the length of the result"
+    );
+    let first = mem::ManuallyDrop::new(first);
+    let second = mem::ManuallyDrop::new(second);
+    let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
+    // # Safety
+    //
+    //   - `A + B == N`, so `first` and `second` fill `array[.. A]` and
+    //     `array[A ..]`, and fresh locals cannot overlap them.
+    //   - the elements are moved out of `first` and `second`, which are never
+    //     dropped.
+    unsafe {
+        let ptr = array.as_mut_ptr() as *mut T;
+        ptr::copy_nonoverlapping(first.as_ptr(), ptr, A);
+        ptr::copy_nonoverlapping(second.as_ptr(), ptr.add(A), B);
+        array.assume_init()
+    }
+}
+
+#[inline]
+/// Convert an array, consumed by value, into an array of a length given as
+/// another const parameter.
+///
+/// Returns `None` if the lengths differ, in which case `array` is dropped. This
+/// is useful in generic code, where two lengths may be known to be equal while
+/// the compiler cannot prove it. The elements are moved, not cloned. See
+/// [`resize_array`] to convert between different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// fn first_row<const N: usize, const M: usize>(matrix: [[u32; N]; M]) -> Option<[u32; M]> {
+///     // The first row can only be returned for square matrices
+///     array_init::reshape(matrix[0])
+/// }
+///
+/// assert_eq!(first_row([[1, 2], [3, 4]]), Some([1, 2]));
+/// assert_eq!(first_row([[1, 2, 3], [4, 5, 6]]), None);
+/// ```
+pub fn reshape<T, const A: usize, const B: usize>(array: [T; A]) -> Option<[T; B]> {
+    if A != B {
+        return None;
+    }
+    let array = mem::ManuallyDrop::new(array);
+    // # Safety
+    //
+    //   - `A == B`, so `[T; A]` and `[T; B]` are the same type, and the
+    //     elements are moved out of `array`, which is never dropped.
+    Some(unsafe { (array.as_ptr() as *const [T; B]).read() })
+}
+
+#[inline]
+/// Convert an array, consumed by value, into an array of another length, by
+/// truncating it or padding it with default values.
+///
+/// The elements are moved, not cloned. If the new length is shorter, the last
+/// elements of `array` are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let longer: [u32; 5] = array_init::resize_array([1, 2, 3]);
+/// assert_eq!(longer, [1, 2, 3, 0, 0]);
+///
+/// let shorter: [u32; 2] = array_init::resize_array([1, 2, 3]);
+/// assert_eq!(shorter, [1, 2]);
+/// ```
+pub fn resize_array<T, const A: usize, const B: usize>(array: [T; A]) -> [T; B]
+where
+    T: Default,
+{
+    // The elements that are not moved into the result are dropped along with
+    // the iterator.
+    from_iter_or_default(IntoIter::new(array))
+}
+
+#[inline]
+/// Initialize an array by cloning the elements of a slice.
+///
+/// Returns `None` if the length of the slice is not the length of the array.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let names = vec![String::from("a"), String::from("b")];
+///
+/// let arr: Option<[String; 2]> = array_init::from_slice(&names);
+/// assert_eq!(arr, Some([String::from("a"), String::from("b")]));
+///
+/// let arr: Option<[String; 3]> = array_init::from_slice(&names);
+/// assert_eq!(arr, None);
+/// ```
+pub fn from_slice<T, const N: usize>(slice: &[T]) -> Option<[T; N]>
+where
+    T: Clone,
+{
+    <&[T; N]>::try_from(slice)
+        .ok()
+        .map(|source| map_array_init(source, T::clone))
+}
+
+#[inline]
+/// Initialize an array by copying the elements of a slice.
+///
+/// Returns `None` if the length of the slice is not the length of the array.
+/// Otherwise, the elements are copied all at once, which is faster than
+/// copying them one by one with [`from_iter`] for large arrays. To copy the
+/// first `N` elements of a longer slice, pass `&slice[.. N]`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let bytes = b"abcd";
+///
+/// let arr: Option<[u8; 4]> = array_init::from_slice_copy(&bytes[..]);
+/// assert_eq!(arr, Some(*b"abcd"));
+///
+/// let arr: Option<[u8; 2]> = array_init::from_slice_copy(&bytes[..]);
+/// assert_eq!(arr, None);
+/// ```
+pub fn from_slice_copy<T, const N: usize>(slice: &[T]) -> Option<[T; N]>
+where
+    T: Copy,
+{
+    if slice.len() != N {
+        return None;
+    }
+    let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
+    // # Safety
+    //
+    //   - `slice` has `N` elements, and a fresh local cannot overlap it.
+    //   - `T: Copy`, so the elements can be duplicated bitwise.
+    unsafe {
+        ptr::copy_nonoverlapping(slice.as_ptr(), array.as_mut_ptr() as *mut T, N);
+        Some(array.assume_init())
+    }
+}
+
+#[inline]
+/// Initialize an array of references to the first elements of a slice.
+///
+/// Returns `None` if the slice is shorter than the array. Unlike
+/// [`from_slice`], the elements are borrowed rather than cloned, and the slice
+/// may be longer than the array.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let names = vec![String::from("a"), String::from("b"), String::from("c")];
+///
+/// let refs: Option<[&String; 2]> = array_init::array_of_refs(&names);
+/// assert_eq!(refs, Some([&names[0], &names[1]]));
+///
+/// let refs: Option<[&String; 4]> = array_init::array_of_refs(&names);
+/// assert_eq!(refs, None);
+/// ```
+pub fn array_of_refs<T, const N: usize>(slice: &[T]) -> Option<[&T; N]> {
+    from_iter(slice)
+}
+
+#[inline]
+/// Initialize an array with the bytes of an integer, in big-endian order.
+///
+/// This is the same as `value.to_be_bytes()`, for integer types given as a
+/// generic parameter.
+///
+/// Fails to compile if `N` is not the size of `T`. Being raised when the
+/// function is instantiated, this error shows up with `cargo build`, but not
+/// with `cargo check`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let bytes: [u8; 4] = array_init::from_be(0x1234_5678_u32);
+/// assert_eq!(bytes, [0x12, 0x34, 0x56, 0x78]);
+/// ```
+///
+/// ```rust,compile_fail
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // A `u32` has 4 bytes
+/// let bytes: [u8; 8] = array_init::from_be(0x1234_5678_u32);
+/// ```
+pub fn from_be<T, const N: usize>(value: T) -> [u8; N]
+where
+    T: Integer,
+{
+    let () = SizeOf::<T, N>::ASSERT_EQ;
+    array_init(|i| value.le_byte(N - 1 - i))
+}
+
+#[inline]
+/// Initialize an array with the bytes of an integer, in little-endian order.
+///
+/// This is the same as `value.to_le_bytes()`, for integer types given as a
+/// generic parameter.
+///
+/// Fails to compile if `N` is not the size of `T`, as for [`from_be`].
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let bytes: [u8; 2] = array_init::from_le(-2_i16);
+/// assert_eq!(bytes, [0xfe, 0xff]);
+/// ```
+///
+/// ```rust,compile_fail
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // An `i16` has 2 bytes
+/// let bytes: [u8; 1] = array_init::from_le(-2_i16);
+/// ```
+pub fn from_le<T, const N: usize>(value: T) -> [u8; N]
+where
+    T: Integer,
+{
+    let () = SizeOf::<T, N>::ASSERT_EQ;
+    array_init(|i| value.le_byte(i))
+}
+
+#[inline]
+/// Initialize an array with consecutive integers, starting from `start`.
+///
+/// This is the same as `array_init(|i| start + i as T)`, without the cast.
+///
+/// # Panics
+///
+/// Panics if the last element, `start + (N - 1)`, overflows `T`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let ports: [u16; 4] = array_init::range_array(8080);
+/// assert_eq!(ports, [8080, 8081, 8082, 8083]);
+///
+/// let tail: [i8; 3] = array_init::range_array(125);
+/// assert_eq!(tail, [125, 126, 127]);
+/// ```
+pub fn range_array<T, const N: usize>(start: T) -> [T; N]
+where
+    T: Integer,
+{
+    array_init_prev(start, |_, &prev| {
+        prev.successor()
+            .expect("the range overflows the integer type")
+    })
+}
+
+/// Checks at compile time that `N` is the size of `T`.
+struct SizeOf<T, const N: usize>(PhantomData<T>);
+
+impl<T, const N: usize> SizeOf<T, N> {
+    // Fails to evaluate, by indexing out of bounds, if the sizes differ
+    const ASSERT_EQ: () = [()][(N != mem::size_of::<T>()) as usize];
+}
+
+/// The primitive integer types, whose bytes can be written into arrays with
+/// [`from_be`] and [`from_le`], and whose ranges can be written into arrays with
+/// [`range_array`].
+///
+/// This trait is sealed: it cannot be implemented outside of this crate.
+pub trait Integer: Copy + integer::Sealed {
+    #[doc(hidden)]
+    /// Returns the byte of weight `256^index`.
+    fn le_byte(self, index: usize) -> u8;
+
+    #[doc(hidden)]
+    /// Returns `self + 1`, or `None` on overflow.
+    fn successor(self) -> Option<Self>;
+}
+
+mod integer {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_integer {
+    ($($type:ty),*) => {$(
+        impl integer::Sealed for $type {}
+
+        impl Integer for $type {
+            #[inline]
+            fn le_byte(self, index: usize) -> u8 {
+                (self >> (8 * index)) as u8
+            }
+
+            #[inline]
+            fn successor(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[inline]
+/// Initialize an array in place, given an initializer expression.
+///
+/// This behaves like [`array_init`], but the array is written into `slot`,
+/// which the caller owns, e.g., as a field of a larger structure, so that it is
+/// never moved. Returns a reference to the now init array. If `slot` was
+/// already init, the previous array is overwritten without being dropped.
+///
+/// Unlike [`array_init`], this does not copy the array once it is initialized:
+/// each element is written once, into `slot`.
+///
+/// If the initializer panics, the elements initialized so far are dropped, and
+/// `slot` is left uninit.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use std::mem::MaybeUninit;
+///
+/// let mut slot: Box<MaybeUninit<[u64; 4096]>> = Box::new(MaybeUninit::uninit());
+///
+/// let table = array_init::array_init_in_place(&mut slot, |i| (i * i) as u64);
+/// assert_eq!(table[12], 144);
+/// ```
+pub fn array_init_in_place<F, T, const N: usize>(
+    slot: &mut MaybeUninit<[T; N]>,
+    mut initializer: F,
+) -> &mut [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    enum Unreachable {}
+
+    // # Safety
+    //
+    //   - pointer to array = *mut [T; N] <-> *mut T = pointer to first element,
+    //     which is valid for writes of `N` elements.
+    //   - once `try_init_in_place` returns, all `N` elements are init.
+    unsafe {
+        try_init_in_place::<Unreachable, _, T, 1>(slot.as_mut_ptr() as *mut T, N, |i| {
+            Ok(initializer(i))
+        })
+        .unwrap_or_else(|unreachable| match unreachable { /* ! */ });
+        &mut *slot.as_mut_ptr()
+    }
+}
+
+#[inline]
+/// Create an array of uninit elements.
+///
+/// This is useful as scratch space, to be initialized element by element and
+/// then converted with [`array_assume_init`].
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use std::mem::MaybeUninit;
+///
+/// let mut scratch: [MaybeUninit<String>; 3] = array_init::uninit_array();
+/// for (i, slot) in scratch.iter_mut().enumerate() {
+///     *slot = MaybeUninit::new(i.to_string());
+/// }
+///
+/// // Safety: all the elements were initialized above
+/// let arr = unsafe { array_init::array_assume_init(scratch) };
+/// assert_eq!(arr, ["0", "1", "2"]);
+/// ```
+pub fn uninit_array<T, const N: usize>() -> [MaybeUninit<T>; N] {
+    // # Safety
+    //
+    //   - an array of `MaybeUninit` does not require initialization.
+    unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() }
+}
+
+#[inline]
+/// Convert an array of `MaybeUninit` elements into an array of init elements.
+///
+/// See [`uninit_array`] for an example.
+///
+/// # Safety
+///
+/// All the elements of `array` must be init, as with
+/// [`MaybeUninit::assume_init`]. Calling this function otherwise is undefined
+/// behavior.
+pub unsafe fn array_assume_init<T, const N: usize>(array: [MaybeUninit<T>; N]) -> [T; N] {
+    // # Safety
+    //
+    //   - `MaybeUninit<T>` has the same layout as `T`, so `[MaybeUninit<T>; N]`
+    //     has the same layout as `[T; N]`.
+    //   - the caller guarantees that all the elements are init.
+    //   - `MaybeUninit` never drops its contents, so `array` does not need to be
+    //     forgotten.
+    (&array as *const [MaybeUninit<T>; N] as *const [T; N]).read()
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+/// Initialize a heap-allocated array given an initializer expression.
+///
+/// This behaves like [`array_init`], but the array is allocated on the heap and
+/// filled in place, so that large arrays never need to fit on the stack.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// // A lookup table too large to comfortably build on the stack
+/// let table: Box<[u8; 1 << 20]> = array_init::array_init_boxed(|i| (i % 256) as u8);
+///
+/// assert_eq!(table[257], 1);
+/// ```
+pub fn array_init_boxed<F, T, const N: usize>(mut initializer: F) -> Box<[T; N]>
+where
+    F: FnMut(usize) -> T,
+{
+    enum Unreachable {}
+
+    try_array_init_boxed_impl(
+        // monomorphise into an infallible version
+        move |i| -> Result<T, Unreachable> { Ok(initializer(i)) },
+    )
+    .unwrap_or_else(
+        // zero-cost unwrap
+        |unreachable| match unreachable { /* ! */ },
+    )
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+/// Initialize a heap-allocated array given an initializer expression that may
+/// fail.
+///
+/// This behaves like [`try_array_init`], but the array is allocated on the heap
+/// and filled in place, like [`array_init_boxed`]. If the initializer fails, the
+/// elements initialized so far are dropped and the allocation is freed.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use std::convert::TryFrom;
+///
+/// let table: Result<Box<[u16; 1 << 16]>, _> =
+///     array_init::try_array_init_boxed(|i| u16::try_from(i));
+/// assert_eq!(table.unwrap()[300], 300);
+///
+/// let table: Result<Box<[u8; 1 << 16]>, _> =
+///     array_init::try_array_init_boxed(|i| u8::try_from(i));
+/// assert!(table.is_err());
+/// ```
+pub fn try_array_init_boxed<Err, F, T, const N: usize>(initializer: F) -> Result<Box<[T; N]>, Err>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    try_array_init_boxed_impl(initializer)
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+/// Initialize a pinned heap-allocated array given an initializer expression.
+///
+/// This behaves like [`array_init_boxed`], and pins the box once it is full.
+/// Each element is moved once, from the value returned by the initializer into
+/// its slot in the box, and never again: the initializer cannot observe the
+/// final address of an element, and once the array is returned, the pin
+/// guarantees that the elements stay at that address until they are dropped.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use std::marker::PhantomPinned;
+///
+/// struct Node {
+///     id: usize,
+///     _pinned: PhantomPinned,
+/// }
+///
+/// let nodes: std::pin::Pin<Box<[Node; 4]>> = array_init::array_init_pinned(|id| Node {
+///     id,
+///     _pinned: PhantomPinned,
+/// });
+/// assert_eq!(nodes[3].id, 3);
+/// ```
+pub fn array_init_pinned<F, T, const N: usize>(initializer: F) -> Pin<Box<[T; N]>>
+where
+    F: FnMut(usize) -> T,
+{
+    Pin::from(array_init_boxed(initializer))
+}
+
+#[cfg(feature = "alloc")]
+/// Split an iterator into arrays.
+///
+/// Returns the arrays filled with successive elements of the iterator, and the
+/// elements left once it can no longer fill an array, which are fewer than `N`.
+/// If `N` is 0, all the elements are left.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let (chunks, tail) = array_init::chunk_iter::<_, u32, 3>(0..8);
+///
+/// assert_eq!(chunks, [[0, 1, 2], [3, 4, 5]]);
+/// assert_eq!(tail, [6, 7]);
+/// ```
+pub fn chunk_iter<Iterable, T, const N: usize>(iterable: Iterable) -> (Vec<[T; N]>, Vec<T>)
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    let mut iterator = iterable.into_iter();
+    if N == 0 {
+        return (Vec::new(), iterator.collect());
+    }
+    let mut chunks = Vec::new();
+    loop {
+        match try_array_init_recover(|_| iterator.next().ok_or(())) {
+            Ok(chunk) => chunks.push(chunk),
+            Err(((), tail)) => return (chunks, tail.into_vec()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+/// Initialize an array given an iterator of fallible values, reporting every
+/// error instead of the first one.
+///
+/// Unlike [`try_from_iter`], which stops at the first error, this pulls all `N`
+/// values from the iterator and returns the index of every `Err`, along with
+/// [`ArrayInitError::Closure`]. If the iterator runs short, the last error is
+/// [`ArrayInitError::TooShort`], at the index of the first missing element.
+/// The successful values are dropped if there is any error.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::ArrayInitError;
+///
+/// let parsed: Result<[u8; 4], _> =
+///     array_init::from_results_all("1 x 3 y".split(' ').map(str::parse::<u8>));
+/// let indices: Vec<usize> = parsed.unwrap_err().iter().map(|(i, _)| *i).collect();
+/// assert_eq!(indices, [1, 3]);
+///
+/// let parsed: Result<[u8; 4], _> =
+///     array_init::from_results_all("1 x".split(' ').map(str::parse::<u8>));
+/// assert!(matches!(
+///     parsed.unwrap_err()[..],
+///     [(1, ArrayInitError::Closure(_)), (2, ArrayInitError::TooShort { got: 2, expected: 4 })]
+/// ));
+/// ```
+pub fn from_results_all<Iterable, T, Err, const N: usize>(
+    iterable: Iterable,
+) -> Result<[T; N], Vec<(usize, ArrayInitError<Err>)>>
+where
+    Iterable: IntoIterator<Item = Result<T, Err>>,
+{
+    let mut iterator = iterable.into_iter();
+    // Only the values before the first error are kept: the later ones are
+    // dropped right away, since the array cannot be returned anymore.
+    let mut prefix = ArrayPrefix::<T, N>::new();
+    let mut errors = Vec::new();
+    for i in 0..N {
+        match iterator.next() {
+            Some(Ok(value)) => {
+                if errors.is_empty() {
+                    // # Safety
+                    //
+                    //   - without errors, `prefix` holds `i < N` elements.
+                    unsafe {
+                        prefix.push_unchecked(value);
+                    }
+                }
+            }
+            Some(Err(err)) => errors.push((i, ArrayInitError::Closure(err))),
+            None => {
+                errors.push((
+                    i,
+                    ArrayInitError::TooShort {
+                        got: i,
+                        expected: N,
+                    },
+                ));
+                break;
+            }
+        }
+    }
+    if errors.is_empty() {
+        // # Safety
+        //
+        //   - without errors, `prefix` holds `N` elements.
+        Ok(unsafe { prefix.into_array_unchecked() })
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Initialize an array given an iterator, spilling to a `Vec` if it yields
+/// more than `N` elements.
+///
+/// Returns [`ArrayOrVec::Inline`] with the elements if there are at most `N` of
+/// them, without allocating, and [`ArrayOrVec::Spilled`] with all of them
+/// otherwise.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let words = array_init::from_iter_spill::<_, _, 4>("a b c".split(' '));
+/// assert!(!words.is_spilled());
+/// assert_eq!(words.as_slice(), ["a", "b", "c"]);
+///
+/// let words = array_init::from_iter_spill::<_, _, 4>("a b c d e".split(' '));
+/// assert!(words.is_spilled());
+/// assert_eq!(words.into_vec(), ["a", "b", "c", "d", "e"]);
+/// ```
+pub fn from_iter_spill<Iterable, T, const N: usize>(iterable: Iterable) -> ArrayOrVec<T, N>
+where
+    Iterable: IntoIterator<Item = T>,
+{
+    let mut iterator = iterable.into_iter();
+    let prefix = array_init_until(|_| iterator.next());
+    if prefix.len() < N {
+        return ArrayOrVec::Inline(prefix);
+    }
+    match iterator.next() {
+        None => ArrayOrVec::Inline(prefix),
+        Some(next) => {
+            let mut vec = prefix.into_vec();
+            vec.reserve(1 + iterator.size_hint().0);
+            vec.push(next);
+            vec.extend(iterator);
+            ArrayOrVec::Spilled(vec)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+/// Move the elements of a `Vec` into an array.
+///
+/// Returns the `Vec` back if its length is not the length of the array. The
+/// elements are moved, not cloned, and the allocation of the `Vec` is freed.
+/// See [`from_vec_boxed`] to reuse it instead.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let words = vec![String::from("a"), String::from("b")];
+///
+/// let arr: Result<[String; 2], _> = array_init::from_vec(words);
+/// assert_eq!(arr.unwrap(), ["a", "b"]);
+///
+/// let arr: Result<[u32; 2], _> = array_init::from_vec(vec![1, 2, 3]);
+/// assert_eq!(arr, Err(vec![1, 2, 3]));
+/// ```
+pub fn from_vec<T, const N: usize>(mut vec: Vec<T>) -> Result<[T; N], Vec<T>> {
+    if vec.len() != N {
+        return Err(vec);
+    }
+    let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
+    // # Safety
+    //
+    //   - `vec` has `N` elements, and a fresh local cannot overlap it.
+    //   - the length of `vec` is set to 0 before it is dropped, so that the
+    //     elements moved to `array` are not dropped twice.
+    unsafe {
+        ptr::copy_nonoverlapping(vec.as_ptr(), array.as_mut_ptr() as *mut T, N);
+        vec.set_len(0);
+        Ok(array.assume_init())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+/// Convert a `Vec` into a heap-allocated array, reusing its allocation.
+///
+/// Returns the `Vec` back if its length is not the length of the array. The
+/// allocation is reused as is if the capacity of the `Vec` is its length, and
+/// shrunk otherwise, which may reallocate.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let values: Vec<u64> = (0..1024).collect();
+///
+/// let table: Box<[u64; 1024]> = array_init::from_vec_boxed(values).unwrap();
+/// assert_eq!(table[42], 42);
+/// ```
+pub fn from_vec_boxed<T, const N: usize>(vec: Vec<T>) -> Result<Box<[T; N]>, Vec<T>> {
+    if vec.len() != N {
+        return Err(vec);
+    }
+    let slice = Box::into_raw(vec.into_boxed_slice());
+    // # Safety
+    //
+    //   - the slice has `N` elements, so it has the layout of `[T; N]`, and it
+    //     was allocated in the way `Box` expects.
+    Ok(unsafe { Box::from_raw(slice as *mut [T; N]) })
+}
+
+#[cfg(feature = "std")]
+/// Initialize an array given an initializer expression that may panic,
+/// reporting the index at which it panicked.
+///
+/// This behaves like [`array_init`], but every call to the initializer runs in
+/// [`std::panic::catch_unwind`]. If one panics, the panic payload is returned
+/// together with the index of the element, and the elements initialized before
+/// that index are dropped.
+///
+/// The initializer is not required to be [`UnwindSafe`](std::panic::UnwindSafe):
+/// as with `catch_unwind(AssertUnwindSafe(..))`, the state it captures may be
+/// left inconsistent by the panic. The panic hook still runs, so the panic
+/// message is printed as usual, and panics are not caught at all with
+/// `panic = "abort"`.
+///
+/// Catching panics for every element prevents some optimizations, so this is
+/// slower than [`array_init`] for cheap initializers; prefer it for debugging.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let values = [1, 2, 0, 4];
+///
+/// let res: Result<[u32; 4], _> = array_init::array_init_catch(|i| 12 / values[i]);
+/// let (index, _payload) = res.unwrap_err();
+/// assert_eq!(index, 2);
+/// ```
+pub fn array_init_catch<F, T, const N: usize>(
+    mut initializer: F,
+) -> Result<[T; N], (usize, Box<dyn Any + Send>)>
+where
+    F: FnMut(usize) -> T,
+{
+    try_array_init_impl::<_, _, T, N, 1>(|i| {
+        panic::catch_unwind(AssertUnwindSafe(|| initializer(i))).map_err(|payload| (i, payload))
+    })
+}
+
+#[cfg(feature = "async")]
+#[inline]
+/// Initialize an array given an asynchronous initializer expression.
+///
+/// The initializer is given the index of the element and returns a future
+/// producing it. The futures are awaited one after the other, in order from
+/// lower to higher indices.
+///
+/// If the returned future is dropped before completion, the elements
+/// initialized so far are dropped.
+///
+/// Requires the `async` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// async fn fetch(i: usize) -> usize {
+///     i * i
+/// }
+///
+/// async fn squares() -> [usize; 5] {
+///     array_init::async_array_init(fetch).await
+/// }
+/// ```
+pub async fn async_array_init<F, Fut, T, const N: usize>(mut initializer: F) -> [T; N]
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = T>,
+{
+    // If an `initializer(i)` call or its future panics, or if we are dropped
+    // while awaiting, `prefix` is dropped along with the elements it holds.
+    // Unlike `UnsafeDropSliceGuard`, it owns the array, so that holding it
+    // across `.await` points does not make the future `!Send`.
+    let mut prefix = ArrayPrefix::<T, N>::new();
+    for i in 0..N {
+        // Invariant: `i` elements have already been initialized
+        let value_i = initializer(i).await;
+        // # Safety
+        //
+        //   - `prefix` holds `i < N` elements.
+        unsafe {
+            prefix.push_unchecked(value_i);
+        }
+    }
+    // # Safety
+    //
+    //   - `prefix` holds `N` elements.
+    unsafe { prefix.into_array_unchecked() }
+}
+
+#[cfg(feature = "async")]
+#[inline]
+/// Initialize an array given an asynchronous initializer expression, giving up
+/// on any element that takes too long.
+///
+/// Like [`async_array_init`], but each future returned by `initializer(i)` is
+/// raced against the future returned by `timer(i)`. If the timer completes
+/// first, the future of the element is dropped, along with the elements
+/// initialized so far, and the index of the element is returned with a
+/// [`TimeoutError`].
+///
+/// The timer is not tied to an async runtime: pass the delay future of the
+/// runtime in use, such as `tokio::time::sleep`. A future that is dropped
+/// before completion is not polled again, so the initializer must not leave
+/// shared state half-updated across its `.await` points.
+///
+/// Requires the `async` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// # use std::future::Future;
+/// # use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+/// #
+/// # fn block_on<Fut: Future>(future: Fut) -> Fut::Output {
+/// #     fn clone(_: *const ()) -> RawWaker {
+/// #         RawWaker::new(std::ptr::null(), &VTABLE)
+/// #     }
+/// #     fn noop(_: *const ()) {}
+/// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+/// #
+/// #     let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// #
+/// use array_init::TimeoutError;
+/// use std::future::{pending, ready};
+///
+/// // `block_on` polls a future to completion, as an async runtime would.
+///
+/// // The elements are ready at once, and the timers never complete
+/// let squares: Result<[usize; 4], _> =
+///     block_on(array_init::async_array_init_timeout(|_| pending(), |i| ready(i * i)));
+/// assert_eq!(squares, Ok([0, 1, 4, 9]));
+///
+/// // The element at index 2 never completes, and its timer completes at once
+/// let stalled: Result<[usize; 4], _> = block_on(array_init::async_array_init_timeout(
+///     |i| async move {
+///         if i < 2 {
+///             pending().await
+///         }
+///     },
+///     |i| async move {
+///         if i < 2 {
+///             i
+///         } else {
+///             pending().await
+///         }
+///     },
+/// ));
+/// assert_eq!(stalled, Err((2, TimeoutError)));
+/// ```
+pub async fn async_array_init_timeout<Tm, D, F, Fut, T, const N: usize>(
+    mut timer: Tm,
+    mut initializer: F,
+) -> Result<[T; N], (usize, TimeoutError)>
+where
+    Tm: FnMut(usize) -> D,
+    D: Future<Output = ()>,
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut prefix = ArrayPrefix::<T, N>::new();
+    for i in 0..N {
+        // Invariant: `i` elements have already been initialized
+        let timeout = Timeout {
+            future: initializer(i),
+            delay: timer(i),
+        };
+        let value_i = timeout.await.map_err(|err| (i, err))?;
+        // # Safety
+        //
+        //   - `prefix` holds `i < N` elements.
+        unsafe {
+            prefix.push_unchecked(value_i);
+        }
+    }
+    // # Safety
+    //
+    //   - `prefix` holds `N` elements.
+    Ok(unsafe { prefix.into_array_unchecked() })
+}
+
+/// Races a future against a delay, used by [`async_array_init_timeout`].
+#[cfg(feature = "async")]
+struct Timeout<Fut, D> {
+    future: Fut,
+    delay: D,
+}
+
+#[cfg(feature = "async")]
+impl<Fut: Future, D: Future<Output = ()>> Future for Timeout<Fut, D> {
+    type Output = Result<Fut::Output, TimeoutError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // # Safety
+        //
+        //   - the fields are never moved out of `self`, and `Timeout` has no
+        //     `Drop` implementation, so pinning projects to both of them.
+        let (future, delay) = unsafe {
+            let this = self.get_unchecked_mut();
+            (
+                Pin::new_unchecked(&mut this.future),
+                Pin::new_unchecked(&mut this.delay),
+            )
+        };
+        if let Poll::Ready(value) = future.poll(cx) {
+            return Poll::Ready(Ok(value));
+        }
+        delay.poll(cx).map(|()| Err(TimeoutError))
+    }
+}
+
+#[cfg(feature = "futures")]
+#[inline]
+/// Initialize an array given a stream.
+///
+/// This is the asynchronous version of [`from_iter`]: the items of the stream
+/// are awaited until the array is full, and `None` is returned if the stream
+/// ends before. The stream is not polled once the array is full. If the
+/// returned future is dropped before completion, the elements initialized so
+/// far are dropped.
+///
+/// Requires the `futures` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// # // The `futures` feature enables `futures-core` under that name
+/// # extern crate futures as futures_core;
+/// #
+/// # use std::future::Future;
+/// # use std::task::{RawWaker, RawWakerVTable, Waker};
+/// #
+/// # fn block_on<Fut: Future>(future: Fut) -> Fut::Output {
+/// #     fn clone(_: *const ()) -> RawWaker {
+/// #         RawWaker::new(std::ptr::null(), &VTABLE)
+/// #     }
+/// #     fn noop(_: *const ()) {}
+/// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+/// #
+/// #     let waker = unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) };
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// #
+/// use futures_core::Stream;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// /// Yields the bytes of a slice, as a reader would.
+/// struct Bytes<'a>(&'a [u8]);
+///
+/// impl Stream for Bytes<'_> {
+///     type Item = u8;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u8>> {
+///         let (&first, rest) = match self.0.split_first() {
+///             Some(split) => split,
+///             None => return Poll::Ready(None),
+///         };
+///         self.0 = rest;
+///         Poll::Ready(Some(first))
+///     }
+/// }
+///
+/// // `block_on` polls a future to completion, as an async runtime would.
+///
+/// let magic: Option<[u8; 4]> = block_on(array_init::from_stream(Bytes(b"\x7fELF\x02")));
+/// assert_eq!(&magic.unwrap(), b"\x7fELF");
+///
+/// let magic: Option<[u8; 4]> = block_on(array_init::from_stream(Bytes(b"\x7f")));
+/// assert_eq!(magic, None);
+/// ```
+pub async fn from_stream<S, T, const N: usize>(stream: S) -> Option<[T; N]>
+where
+    S: Stream<Item = T>,
+{
+    let mut stream = stream;
+    // # Safety
+    //
+    //   - `stream` is shadowed, so that it is never moved again.
+    let mut stream = unsafe { Pin::new_unchecked(&mut stream) };
+    let mut prefix = ArrayPrefix::<T, N>::new();
+    for _ in 0..N {
+        // Invariant: `prefix` holds the elements initialized so far
+        let value_i = Next {
+            stream: stream.as_mut(),
+        }
+        .await?;
+        // # Safety
+        //
+        //   - `prefix` holds less than `N` elements.
+        unsafe {
+            prefix.push_unchecked(value_i);
+        }
+    }
+    // # Safety
+    //
+    //   - `prefix` holds `N` elements.
+    Some(unsafe { prefix.into_array_unchecked() })
+}
+
+/// Awaits the next item of a stream, used by [`from_stream`].
+#[cfg(feature = "futures")]
+struct Next<'a, S> {
+    stream: Pin<&'a mut S>,
+}
+
+#[cfg(feature = "futures")]
+impl<S: Stream> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(feature = "serde")]
+/// Deserialize an array without requiring `T: Default`.
+///
+/// This is meant to be used as a field attribute:
+/// `#[serde(deserialize_with = "array_init::deserialize_array")]`. Like serde
+/// does for the arrays it supports, the array is deserialized as a tuple of `N`
+/// elements. Fewer or more than `N` elements result in an error, and the
+/// elements deserialized so far are dropped.
+///
+/// Requires the `serde` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// # extern crate serde;
+/// #
+/// use serde::de::value::{Error, SeqDeserializer};
+///
+/// let deserializer = SeqDeserializer::<_, Error>::new(0..64);
+/// let arr: [u32; 64] = array_init::deserialize_array(deserializer).unwrap();
+///
+/// assert!(arr.iter().enumerate().all(|(i, &x)| x == i as u32));
+/// ```
+pub fn deserialize_array<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    use ::core::{fmt, marker::PhantomData};
+    use serde::de::{Error, IgnoredAny, SeqAccess, Visitor};
+
+    struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+    impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        type Value = [T; N];
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(formatter, "an array of length {}", N)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<[T; N], A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let array = try_array_init_impl::<_, _, T, N, 1>(|i| {
+                seq.next_element()?
+                    .ok_or_else(|| Error::invalid_length(i, &self))
+            })?;
+            if seq.next_element::<IgnoredAny>()?.is_some() {
+                return Err(Error::invalid_length(N + 1, &self));
+            }
+            Ok(array)
+        }
+    }
+
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}
+
+#[cfg(feature = "bytemuck")]
+#[inline]
+/// Initialize an array of plain old data from its bytes.
+///
+/// Returns `None` if the length of `bytes` is not the size of the array.
+/// Otherwise, the bytes are copied into the array all at once, in the native
+/// byte order. Since the array is returned by value, `bytes` does not need to
+/// be aligned for `T`.
+///
+/// Requires the `bytemuck` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let bytes = [1, 0, 2, 0, 3, 0];
+///
+/// let values: Option<[u16; 3]> = array_init::from_bytes(&bytes);
+/// if cfg!(target_endian = "little") {
+///     assert_eq!(values, Some([1, 2, 3]));
+/// }
+///
+/// let values: Option<[u16; 2]> = array_init::from_bytes(&bytes);
+/// assert_eq!(values, None);
+/// ```
+pub fn from_bytes<T, const N: usize>(bytes: &[u8]) -> Option<[T; N]>
+where
+    T: Pod,
+{
+    if bytes.len() != mem::size_of::<[T; N]>() {
+        return None;
+    }
+    // # Safety
+    //
+    //   - `bytes` has the size of `[T; N]`, and is read without alignment
+    //     requirement.
+    //   - `T: Pod`, so any bytes are a valid `T`.
+    Some(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const [T; N]) })
+}
+
+#[cfg(feature = "rand")]
+#[inline]
+/// Initialize an array with random values.
+///
+/// Each element is sampled from the [`Standard`](rand::distributions::Standard)
+/// distribution, in order from lower to higher indices.
+///
+/// Requires the `rand` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// # extern crate rand;
+/// #
+/// let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+/// let arr: [u32; 4] = array_init::array_init_random(&mut rng);
+///
+/// assert_eq!(arr, [0, 1, 2, 3]);
+/// ```
+pub fn array_init_random<R, T, const N: usize>(rng: &mut R) -> [T; N]
+where
+    R: rand::Rng + ?Sized,
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    array_init(|_| rng.gen())
+}
+
+#[cfg(feature = "rand")]
+#[inline]
+/// Initialize an array with values sampled from a distribution.
+///
+/// The elements are sampled in order from lower to higher indices.
+///
+/// Requires the `rand` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// # extern crate rand;
+/// #
+/// use rand::distributions::Uniform;
+///
+/// let mut rng = rand::rngs::mock::StepRng::new(0, 1 << 60);
+/// let arr: [u8; 8] = array_init::array_init_sample(Uniform::new(0, 10), &mut rng);
+///
+/// assert!(arr.iter().all(|&x| x < 10));
+/// ```
+pub fn array_init_sample<D, R, T, const N: usize>(distribution: D, rng: &mut R) -> [T; N]
+where
+    D: rand::distributions::Distribution<T>,
+    R: rand::Rng + ?Sized,
+{
+    array_init(|_| distribution.sample(rng))
+}
+
+#[cfg(feature = "rayon")]
+/// Initialize an array given an initializer expression, in parallel.
+///
+/// The initializer is given the index of the element, and is called on the
+/// threads of the current rayon thread pool. Unlike with [`array_init`], there
+/// is no guarantee about the order in which the elements are initialized, hence
+/// the initializer being `Fn`.
+///
+/// If the initializer panics, the panic is propagated once the other threads are
+/// done, and all the elements initialized so far are dropped, in no particular
+/// order.
+///
+/// Requires the `rayon` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// let arr: [u64; 1000] = array_init::array_init_par(|i| (0..i as u64).sum());
+///
+/// assert_eq!(arr[10], 45);
+/// ```
+pub fn array_init_par<F, T, const N: usize>(initializer: F) -> [T; N]
+where
+    F: Fn(usize) -> T + Sync,
+    T: Send,
+{
+    /// A pointer to the first element of the array, shared by the threads
+    /// initializing disjoint ranges of it.
+    struct SharedPtr<T>(*mut T);
+
+    impl<T> Clone for SharedPtr<T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<T> Copy for SharedPtr<T> {}
+
+    // # Safety
+    //
+    //   - the pointer is only used to write and drop elements of type `T`, each
+    //     of them from a single thread.
+    unsafe impl<T: Send> Send for SharedPtr<T> {}
+    unsafe impl<T: Send> Sync for SharedPtr<T> {}
+
+    /// # Safety
+    ///
+    ///   - `base_ptr[start .. end]` must be a slice of init elements, which are
+    ///     dropped if/when `UnsafeDropRangeGuard` is dropped.
+    struct UnsafeDropRangeGuard<T> {
+        base_ptr: SharedPtr<T>,
+        start: usize,
+        end: usize,
+    }
+
+    impl<T> Drop for UnsafeDropRangeGuard<T> {
+        fn drop(&mut self) {
+            unsafe {
+                // # Safety
+                //
+                //   - the contract of the struct guarantees that this is sound
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    self.base_ptr.0.add(self.start),
+                    self.end - self.start,
+                ));
+            }
+        }
+    }
+
+    /// Initializes `base_ptr[start .. end]`, splitting the range in two halves
+    /// initialized in parallel, until it is no longer than `min_len`.
+    ///
+    /// # Safety
+    ///
+    ///   - `base_ptr[start .. end]` must be valid for writes, and not be
+    ///     accessed by anything else until the returned guard is dropped.
+    unsafe fn init_range<F, T>(
+        base_ptr: SharedPtr<T>,
+        start: usize,
+        end: usize,
+        min_len: usize,
+        initializer: &F,
+    ) -> UnsafeDropRangeGuard<T>
+    where
+        F: Fn(usize) -> T + Sync,
+        T: Send,
+    {
+        if end - start <= min_len {
+            enum Unreachable {}
+
+            try_init_in_place::<Unreachable, _, T, 1>(base_ptr.0.add(start), end - start, |i| {
+                Ok(initializer(start + i))
+            })
+            .unwrap_or_else(|unreachable| match unreachable { /* ! */ });
+        } else {
+            // If one half panics, the guard of the other half is dropped before
+            // the panic is propagated, dropping its elements.
+            let mid = start + (end - start) / 2;
+            let (left, right) = rayon::join(
+                || init_range(base_ptr, start, mid, min_len, initializer),
+                || init_range(base_ptr, mid, end, min_len, initializer),
+            );
+            // The two halves are contiguous: merge their guards.
+            mem::forget(left);
+            mem::forget(right);
+        }
+        UnsafeDropRangeGuard {
+            base_ptr,
+            start,
+            end,
+        }
+    }
+
+    let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
+    let min_len = (N / (4 * rayon::current_num_threads())).max(1);
+    // # Safety
+    //
+    //   - pointer to array = *mut [T; N] <-> *mut T = pointer to first element,
+    //     which is valid for writes of `N` elements.
+    //   - once `init_range` returns, all `N` elements are init, and the guard is
+    //     forgotten so that they are not dropped.
+    unsafe {
+        let guard = init_range(
+            SharedPtr(array.as_mut_ptr() as *mut T),
+            0,
+            N,
+            min_len,
+            &initializer,
+        );
+        mem::forget(guard);
+        array.assume_init()
+    }
+}
+
+#[inline]
+/// Returns `true` if arrays of `T` are initialized without keeping track of
+/// the initialized elements.
+///
+/// This is the case when `T` does not need to be dropped (see
+/// [`core::mem::needs_drop`]): a panic or an error midway cannot leak anything,
+/// so [`array_init`], [`try_array_init`] and most of the functions built on them
+/// take a faster path. With the `leak-on-panic` feature, this is the case for
+/// all types. See [`assert_fast_path!`] to check it at compile time.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// assert!(array_init::uses_fast_path::<u64>());
+///
+/// // `false` unless the `leak-on-panic` feature is enabled
+/// let fast = array_init::uses_fast_path::<String>();
+/// ```
+pub const fn uses_fast_path<T>() -> bool {
+    cfg!(feature = "leak-on-panic") || !mem::needs_drop::<T>()
+}
+
+/// Checks at compile time that arrays of a type are initialized without keeping
+/// track of the initialized elements.
+///
+/// This fails to compile if [`uses_fast_path`] returns `false` for the type,
+/// i.e., if it needs to be dropped and the `leak-on-panic` feature is not
+/// enabled. The type cannot depend on generic parameters.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # #[macro_use]
+/// # extern crate array_init;
+/// #
+/// array_init::assert_fast_path!([u8; 16]);
+/// ```
+///
+#[cfg_attr(not(feature = "leak-on-panic"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "leak-on-panic", doc = "```rust")]
+/// # #![allow(unused)]
+/// # #[macro_use]
+/// # extern crate array_init;
+/// #
+/// array_init::assert_fast_path!(String);
+/// ```
+#[macro_export]
+macro_rules! assert_fast_path {
+    ($type:ty) => {
+        // Fails to evaluate, by indexing out of bounds, if the type needs drop
+        const _: () = [()][!$crate::uses_fast_path::<$type>() as usize];
+    };
+}
+
+/// Initialize an array given an initializer expression, in a `const` context.
+///
+/// This takes the length of the array and a closure-like initializer, which is
+/// given the index of the element, or the path of a `const fn` taking the index,
+/// as with [`array_init_fn_table`]. Unlike [`array_init`], it can be used to
+/// initialize `const` and `static` items, provided that the initializer can be
+/// evaluated at compile time. We will always initialize the elements in order.
+///
+/// The elements must be `Copy`: the array is first filled with the element at
+/// index 0, which is then overwritten. As a consequence, the initializer is
+/// evaluated for index 0 even if the length is 0.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # #[macro_use]
+/// # extern crate array_init;
+/// #
+/// const fn square(i: usize) -> u32 {
+///     (i * i) as u32
+/// }
+///
+/// static SQUARES: [u32; 50] = array_init::const_array_init!(50, |i| square(i));
+/// static SAME_SQUARES: [u32; 50] = array_init::const_array_init!(50, square);
+///
+/// assert!(SQUARES.iter().enumerate().all(|(i, &x)| x == (i * i) as u32));
+/// assert_eq!(SQUARES, SAME_SQUARES);
+/// ```
+#[macro_export]
+macro_rules! const_array_init {
+    ($len:expr, $initializer:path) => {
+        $crate::const_array_init!($len, |index| $initializer(index))
+    };
+    ($len:expr, |$index:ident| $initializer:expr) => {{
+        // The length may be 0 or 1, which lints would otherwise complain about
+        #[allow(unused_comparisons, clippy::zero_repeat_side_effects)]
+        let array = {
+            let mut array = [{
+                let $index: usize = 0;
+                $initializer
+            }; $len];
+            let mut index = 1;
+            while index < $len {
+                array[index] = {
+                    let $index: usize = index;
+                    $initializer
+                };
+                index += 1;
+            }
+            array
+        };
+        array
+    }};
+}
+
+/// Copy a slice into an array, in a `const` context.
+///
+/// This takes the slice and the length of the array. Unlike [`from_slice_copy`],
+/// it can be used to initialize `const` and `static` items from slices known at
+/// compile time, e.g., `const` items or byte string literals. The elements must
+/// be `Copy`.
+///
+/// The length of the slice is checked against the length of the array: if they
+/// differ, the evaluation fails, which is a compilation error in a `const`
+/// context, and a panic otherwise. The slice must not be empty, as with
+/// [`const_array_init!`].
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # #[macro_use]
+/// # extern crate array_init;
+/// #
+/// const PRIMES: &[u32] = &[2, 3, 5, 7, 11];
+///
+/// static FIRST_PRIMES: [u32; 5] = array_init::array_from_slice!(PRIMES, 5);
+/// static MAGIC: [u8; 4] = array_init::array_from_slice!(b"\x7fELF", 4);
+///
+/// assert_eq!(FIRST_PRIMES, [2, 3, 5, 7, 11]);
+/// assert_eq!(&MAGIC, b"\x7fELF");
+/// ```
+///
+/// ```rust,compile_fail
+/// # #![allow(unused)]
+/// # #[macro_use]
+/// # extern crate array_init;
+/// #
+/// const PRIMES: &[u32] = &[2, 3, 5, 7, 11];
+///
+/// // The slice has 5 elements
+/// static FIRST_PRIMES: [u32; 4] = array_init::array_from_slice!(PRIMES, 4);
+/// ```
+#[macro_export]
+macro_rules! array_from_slice {
+    ($slice:expr, $len:expr) => {{
+        let slice: &[_] = $slice;
+        // Fails to evaluate, by indexing out of bounds, if the lengths differ
+        let () = [()][(slice.len() != $len) as usize];
+        $crate::const_array_init!($len, |index| slice[index])
+    }};
+}
+
+/// Initialize an array of the given length given an initializer expression.
+///
+/// This is [`array_init`] with the length written inline, for expressions
+/// whose type cannot be inferred from their context, e.g., arguments to generic
+/// functions. The length must be a constant.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # #[macro_use]
+/// # extern crate array_init;
+/// #
+/// let total: u32 = array_init::array_init_sized!(8, |i| i as u32).iter().sum();
+///
+/// assert_eq!(total, 28);
+/// ```
+#[macro_export]
+macro_rules! array_init_sized {
+    ($len:expr, $initializer:expr) => {
+        $crate::array_init::<_, _, { $len }>($initializer)
+    };
+}
+
+/// An index that is in bounds of arrays of length `N`.
+///
+/// Tokens are only handed out by [`array_init_token`], so they can index
+/// arrays of length `N` without bounds checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Index<const N: usize>(usize);
+
+impl<const N: usize> Index<N> {
+    /// Returns the index as a `usize`, which is less than `N`.
+    #[inline]
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    /// Returns a reference to the element of `array` at this index.
+    #[inline]
+    pub fn of<T>(self, array: &[T; N]) -> &T {
+        // # Safety
+        //
+        //   - `Index<N>` is only built by `array_init_token` with `i < N`.
+        unsafe { array.get_unchecked(self.0) }
+    }
+
+    /// Returns a mutable reference to the element of `array` at this index.
+    #[inline]
+    pub fn of_mut<T>(self, array: &mut [T; N]) -> &mut T {
+        // # Safety
+        //
+        //   - `Index<N>` is only built by `array_init_token` with `i < N`.
+        unsafe { array.get_unchecked_mut(self.0) }
+    }
+}
+
+/// An initializer expression, which can be converted into an array.
+///
+/// This allows initializing arrays with [`From`]/[`Into`], e.g., in generic
+/// code. The conversion calls [`array_init`] with the wrapped initializer.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::FromFn;
+///
+/// let arr: [u32; 4] = FromFn(|i| i as u32).into();
+/// assert_eq!(arr, [0, 1, 2, 3]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FromFn<F>(pub F);
+
+impl<F, T, const N: usize> From<FromFn<F>> for [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    #[inline]
+    fn from(FromFn(initializer): FromFn<F>) -> Self {
+        array_init(initializer)
+    }
+}
+
+/// Extension trait to collect iterators into arrays.
+///
+/// This trait is implemented for all iterators.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::ArrayInitIteratorExt;
+///
+/// let arr = (1..).map(|i| i * i).collect_array::<4>();
+/// assert_eq!(arr, Some([1, 4, 9, 16]));
+/// ```
+pub trait ArrayInitIteratorExt: Iterator + Sized {
+    #[inline]
+    /// Collect the iterator into an array.
+    ///
+    /// See [`from_iter`].
+    fn collect_array<const N: usize>(self) -> Option<[Self::Item; N]> {
+        from_iter(self)
+    }
+
+    #[inline]
+    /// Collect the iterator into an array, returning the number of elements that
+    /// were consumed if the iterator runs short.
+    ///
+    /// See [`from_iter_remainder`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![allow(unused)]
+    /// # extern crate array_init;
+    /// #
+    /// use array_init::ArrayInitIteratorExt;
+    ///
+    /// assert_eq!((0..3).try_collect_array::<5>(), Err(3));
+    /// ```
+    fn try_collect_array<const N: usize>(self) -> Result<[Self::Item; N], usize> {
+        from_iter_remainder(self).map_err(|(consumed, _)| consumed)
+    }
+
+    #[inline]
+    /// Returns an iterator over arrays filled with successive elements of the
+    /// iterator.
+    ///
+    /// The elements left once the iterator can no longer fill an array are kept
+    /// in the adaptor, see [`ArrayChunks::remainder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![allow(unused)]
+    /// # extern crate array_init;
+    /// #
+    /// use array_init::ArrayInitIteratorExt;
+    ///
+    /// let mut chunks = (0..8).chunk_arrays::<3>();
+    /// assert_eq!(chunks.next(), Some([0, 1, 2]));
+    /// assert_eq!(chunks.next(), Some([3, 4, 5]));
+    /// assert_eq!(chunks.next(), None);
+    /// assert_eq!(chunks.remainder(), [6, 7]);
+    /// ```
+    fn chunk_arrays<const N: usize>(self) -> ArrayChunks<Self, N> {
+        assert!(N != 0, "chunk size must be non-zero");
+        ArrayChunks {
+            iterator: self,
+            remainder: None,
+        }
+    }
+}
+
+impl<I: Iterator> ArrayInitIteratorExt for I {}
+
+#[inline]
+fn array_init_impl<F, T, const N: usize, const D: i8>(mut initializer: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    enum Unreachable {}
+
+    try_array_init_impl::<_, _, T, N, D>(
+        // monomorphise into an infallible version
+        move |i| -> Result<T, Unreachable> { Ok(initializer(i)) },
+    )
+    .unwrap_or_else(
+        // zero-cost unwrap
+        |unreachable| match unreachable { /* ! */ },
+    )
+}
+
+#[inline]
+fn try_array_init_impl<Err, F, T, const N: usize, const D: i8>(
+    initializer: F,
+) -> Result<[T; N], Err>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
+    // # Safety
+    //
+    //   - pointer to array = *mut [T; N] <-> *mut T = pointer to first element,
+    //     which is valid for writes of `N` elements.
+    //   - if `try_init_in_place` returns `Ok`, all `N` elements are init.
+    unsafe {
+        try_init_in_place::<Err, F, T, D>(array.as_mut_ptr() as *mut T, N, initializer)?;
+        Ok(array.assume_init())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+fn try_array_init_boxed_impl<Err, F, T, const N: usize>(initializer: F) -> Result<Box<[T; N]>, Err>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    /// # Safety
+    ///
+    ///   - `ptr` must have been allocated with the global allocator using
+    ///     `layout`, unless `layout` is zero-sized.
+    ///
+    ///   - the allocation must not be used after `UnsafeDeallocGuard` is dropped.
+    struct UnsafeDeallocGuard {
+        ptr: *mut u8,
+        layout: Layout,
+    }
+
+    impl Drop for UnsafeDeallocGuard {
+        fn drop(&mut self) {
+            if self.layout.size() != 0 {
+                unsafe {
+                    // # Safety
+                    //
+                    //   - the contract of the struct guarantees that this is sound
+                    alloc::alloc::dealloc(self.ptr, self.layout);
+                }
+            }
+        }
+    }
+
+    let layout = Layout::new::<[T; N]>();
+    let ptr: *mut [T; N] = if layout.size() == 0 {
+        ptr::NonNull::dangling().as_ptr()
+    } else {
+        // # Safety
+        //
+        //   - `layout` is not zero-sized.
+        let ptr = unsafe { alloc::alloc::alloc(layout) };
+        if ptr.is_null() {
+            alloc::alloc::handle_alloc_error(layout);
+        }
+        ptr as *mut [T; N]
+    };
+    // If the initializer panics or fails, `dealloc_guard` frees the allocation
+    // once the initialized elements have been dropped.
+    let dealloc_guard = UnsafeDeallocGuard {
+        ptr: ptr as *mut u8,
+        layout,
+    };
+
+    // # Safety
+    //
+    //   - `ptr` is valid for writes of `[T; N]`, i.e., of `N` elements of type `T`.
+    //   - if `try_init_in_place` returns `Ok`, all `N` elements are init, and
+    //     `ptr` was allocated in the way `Box` expects for `[T; N]`.
+    unsafe {
+        try_init_in_place::<Err, F, T, 1>(ptr as *mut T, N, initializer)?;
+        mem::forget(dealloc_guard);
+        Ok(Box::from_raw(ptr))
+    }
+}
+
+/// Initializes `base_ptr[.. len]` in place, calling the initializer with the
+/// indices `0 .. len`. For `D < 0`, the value for index `i` is written to
+/// `base_ptr[len - 1 - i]`.
+///
+/// On `Err` or panic, the elements initialized so far are dropped, leaving
+/// `base_ptr[.. len]` uninit.
+///
+/// For zero-sized `T`, the pointer arithmetic does not move the pointer and the
+/// writes are no-ops, but the slices that get dropped still have as many
+/// elements as were initialized, so each element is dropped exactly once.
+///
+/// # Safety
+///
+///   - `base_ptr` must be valid for writes of `len` elements of type `T`.
+#[inline]
+unsafe fn try_init_in_place<Err, F, T, const D: i8>(
+    base_ptr: *mut T,
+    len: usize,
+    mut initializer: F,
+) -> Result<(), Err>
+where
+    F: FnMut(usize) -> Result<T, Err>,
+{
+    let mut ptr_i = base_ptr;
+
+    // The implementation differentiates two cases:
+    //   A) `T` does not need to be dropped. Even if the initializer panics
+    //      or returns `Err` we will not leak memory. With the `leak-on-panic`
+    //      feature, we take this path for all types, leaking the elements
+    //      initialized so far if the initializer panics.
+    //   B) `T` needs to be dropped. We must keep track of which elements have
+    //      been initialized so far, and drop them if we encounter a panic or `Err` midway.
+    if uses_fast_path::<T>() {
+        // # Safety
+        //
+        //   - for D > 0, we are within the array since we start from the
+        //     beginning of the array, and we have `0 <= i < len`.
+        //   - for D < 0, we start at the end of the array and go back one
+        //     place before writing, going back `len` times in total, finishing
+        //     at the start of the array.
+        //   - on `Err`, `i` elements have been initialized, starting at
+        //     `base_ptr` for D > 0, and at `ptr_i` for D < 0.
+        if D < 0 {
+            ptr_i = ptr_i.add(len);
+        }
+        for i in 0..len {
+            let value_i = match initializer(i) {
+                Ok(value_i) => value_i,
+                Err(err) => {
+                    // This is a no-op unless `T` needs to be dropped.
+                    let initialized = if D < 0 { ptr_i } else { base_ptr };
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(initialized, i));
+                    return Err(err);
+                }
+            };
+            // We overwrite *ptr_i previously undefined value without reading or dropping it.
+            if D < 0 {
+                ptr_i = ptr_i.sub(1);
+            }
+            ptr_i.write(value_i);
+            if D > 0 {
+                ptr_i = ptr_i.add(1);
+            }
+        }
+        Ok(())
+    } else {
+        // else: `mem::needs_drop::<T>()`
+
+        //  If the `initializer(i)` call panics, `panic_guard` is dropped,
+        //  dropping `array[.. initialized_count]` => no memory leak!
+        //
+        // # Safety
+        //
+        //  1. - For D > 0, by construction, array[.. initiliazed_count] only
+        //       contains init elements, thus there is no risk of dropping
+        //       uninit data;
+        //     - For D < 0, by construction, array[len - initialized_count..] only
+        //       contains init elements.
+        //
+        //  2. - for D > 0, we are within the array since we start from the
+        //       beginning of the array, and we have `0 <= i < len`.
+        //     - for D < 0, we start at the end of the array and go back one
+        //       place before writing, going back `len` times in total, finishing
+        //       at the start of the array.
+        //
+        if D < 0 {
+            ptr_i = ptr_i.add(len);
+        }
+        let mut panic_guard = UnsafeDropSliceGuard {
+            base_ptr: ptr_i,
+            initialized_count: 0,
+        };
+
+        for i in 0..len {
+            // Invariant: `i` elements have already been initialized
+            panic_guard.initialized_count = i;
+            // If this panics or fails, `panic_guard` is dropped, thus
+            // dropping the elements in `base_ptr[.. i]` for D > 0 or
+            // `base_ptr[len - i..]` for D < 0.
+            let value_i = initializer(i)?;
+            // this cannot panic
+            // the previously uninit value is overwritten without being read or dropped
+            if D < 0 {
+                ptr_i = ptr_i.sub(1);
+                panic_guard.base_ptr = ptr_i;
+            }
+            ptr_i.write(value_i);
+            if D > 0 {
+                ptr_i = ptr_i.add(1);
+            }
+        }
+        // From now on, the code can no longer `panic!`, let's take the
+        // symbolic ownership back
+        mem::forget(panic_guard);
+
+        Ok(())
+    }
+}
+
+/// # Safety
+///
+///   - `base_ptr[.. initialized_count]` must be a slice of init elements...
+///
+///   - ... that must be sound to `ptr::drop_in_place` if/when
+///     `UnsafeDropSliceGuard` is dropped: "symbolic ownership"
+struct UnsafeDropSliceGuard<Item> {
+    base_ptr: *mut Item,
+    initialized_count: usize,
+}
+
+impl<Item> Drop for UnsafeDropSliceGuard<Item> {
+    fn drop(&mut self) {
+        unsafe {
+            // # Safety
+            //
+            //   - the contract of the struct guarantees that this is sound
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.base_ptr,
+                self.initialized_count,
+            ));
+        }
+    }
+}
+
+/// The first elements of an array of length `N`, the others being uninit.
+///
+/// This is returned by [`try_array_init_recover`] when the initializer fails, so
+/// that the elements initialized before the failure are not lost, and by
+/// [`array_init_until`]. They are dropped along with the `ArrayPrefix`, unless
+/// they are moved out of it, e.g., with [`IntoIterator::into_iter`].
+pub struct ArrayPrefix<T, const N: usize> {
+    array: MaybeUninit<[T; N]>,
+    /// # Safety
+    ///
+    ///   - `array[.. len]` must be a slice of init elements, owned by the prefix.
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayPrefix<T, N> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            array: MaybeUninit::uninit(),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    /// Returns the number of elements in the prefix.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    /// Returns `true` if the prefix contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    /// Returns a slice containing the elements of the prefix.
+    pub fn as_slice(&self) -> &[T] {
+        // # Safety
+        //
+        //   - the contract of the `len` field guarantees that this is sound
+        unsafe { &*ptr::slice_from_raw_parts(self.array.as_ptr() as *const T, self.len) }
+    }
+
+    #[inline]
+    /// Returns a mutable slice containing the elements of the prefix.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // # Safety
+        //
+        //   - the contract of the `len` field guarantees that this is sound
+        unsafe { &mut *ptr::slice_from_raw_parts_mut(self.array.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Moves the elements of the prefix into a `Vec`.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![allow(unused)]
+    /// # extern crate array_init;
+    /// #
+    /// let res: Result<[String; 4], _> =
+    ///     array_init::try_array_init_recover(|i| if i < 2 { Ok(i.to_string()) } else { Err(()) });
+    ///
+    /// let (_, prefix) = res.unwrap_err();
+    /// assert_eq!(prefix.into_vec(), ["0", "1"]);
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len);
+        let this = mem::ManuallyDrop::new(self);
+        // # Safety
+        //
+        //   - the elements are moved to `vec`, and `this` is not dropped, so
+        //     that they are not dropped twice.
+        unsafe {
+            ptr::copy_nonoverlapping(this.array.as_ptr() as *const T, vec.as_mut_ptr(), this.len);
+            vec.set_len(this.len);
+        }
+        vec
+    }
+
+    /// Appends an element to the prefix.
+    ///
+    /// # Safety
+    ///
+    ///   - the prefix must hold less than `N` elements.
+    #[inline]
+    unsafe fn push_unchecked(&mut self, value: T) {
+        (self.array.as_mut_ptr() as *mut T)
+            .add(self.len)
+            .write(value);
+        self.len += 1;
+    }
+
+    /// Moves the elements out of the prefix.
+    ///
+    /// # Safety
+    ///
+    ///   - the prefix must hold `N` elements.
+    #[inline]
+    unsafe fn into_array_unchecked(self) -> [T; N] {
+        let this = mem::ManuallyDrop::new(self);
+        this.array.as_ptr().read()
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayPrefix<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            // # Safety
+            //
+            //   - the contract of the `len` field guarantees that this is sound
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayPrefix<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayPrefix<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T, N> {
+        let this = mem::ManuallyDrop::new(self);
+        // # Safety
+        //
+        //   - the elements are moved to the iterator, and `this` is not
+        //     dropped, so that they are not dropped twice.
+        //   - the contract of the `len` field guarantees that `array[.. len]`
+        //     is init.
+        IntoIter {
+            array: unsafe { ptr::read(&this.array) },
+            alive: 0..this.len,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Elements stored inline when there are at most `N` of them, or in a `Vec`
+/// otherwise.
+///
+/// This is returned by [`from_iter_spill`].
+///
+/// Requires the `alloc` feature.
+#[derive(Debug)]
+pub enum ArrayOrVec<T, const N: usize> {
+    /// At most `N` elements, stored inline.
+    Inline(ArrayPrefix<T, N>),
+    /// More than `N` elements, stored on the heap.
+    Spilled(Vec<T>),
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> ArrayOrVec<T, N> {
+    #[inline]
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    #[inline]
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    /// Returns `true` if the elements are stored in a `Vec`.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, ArrayOrVec::Spilled(_))
+    }
+
+    #[inline]
+    /// Returns a slice containing the elements.
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            ArrayOrVec::Inline(prefix) => prefix.as_slice(),
+            ArrayOrVec::Spilled(vec) => vec,
+        }
+    }
+
+    #[inline]
+    /// Returns a mutable slice containing the elements.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match self {
+            ArrayOrVec::Inline(prefix) => prefix.as_mut_slice(),
+            ArrayOrVec::Spilled(vec) => vec,
+        }
+    }
+
+    #[inline]
+    /// Returns the array if there are exactly `N` elements stored inline, or
+    /// the elements otherwise.
+    pub fn into_array(self) -> Result<[T; N], Self> {
+        match self {
+            ArrayOrVec::Inline(prefix) if prefix.len() == N => {
+                // # Safety
+                //
+                //   - `prefix` holds `N` elements.
+                Ok(unsafe { prefix.into_array_unchecked() })
+            }
+            this => Err(this),
+        }
+    }
+
+    #[inline]
+    /// Moves the elements into a `Vec`.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            ArrayOrVec::Inline(prefix) => prefix.into_vec(),
+            ArrayOrVec::Spilled(vec) => vec,
+        }
+    }
+}
+
+/// A builder for an array of length `N`, filled one element at a time.
+///
+/// This is useful when the elements come from several places, instead of from a
+/// single closure or iterator. The elements pushed so far are dropped along with
+/// the builder if it is not built.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![allow(unused)]
+/// # extern crate array_init;
+/// #
+/// use array_init::ArrayBuilder;
+///
+/// let mut builder = ArrayBuilder::<String, 3>::new();
+/// builder.push(String::from("a")).unwrap();
+/// builder.push(String::from("b")).unwrap();
+///
+/// // The builder is not full yet
+/// let mut builder = builder.build().unwrap_err();
+/// builder.push(String::from("c")).unwrap();
+///
+/// // The builder is full, the value is handed back
+/// assert_eq!(builder.push(String::from("d")), Err(String::from("d")));
+///
+/// assert_eq!(builder.build().unwrap(), ["a", "b", "c"]);
+/// ```
+pub struct ArrayBuilder<T, const N: usize> {
+    prefix: ArrayPrefix<T, N>,
+}
+
+impl<T, const N: usize> ArrayBuilder<T, N> {
+    #[inline]
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self {
+            prefix: ArrayPrefix::new(),
+        }
+    }
+
+    #[inline]
+    /// Appends an element, or hands it back if the builder is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        // # Safety
+        //
+        //   - the builder is not full.
+        unsafe { self.prefix.push_unchecked(value) };
+        Ok(())
+    }
+
+    #[inline]
+    /// Returns the number of elements pushed so far.
+    pub fn len(&self) -> usize {
+        self.prefix.len()
+    }
+
+    #[inline]
+    /// Returns `true` if no element was pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_empty()
+    }
+
+    #[inline]
+    /// Returns `true` if `N` elements were pushed.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    #[inline]
+    /// Returns a slice containing the elements pushed so far.
+    pub fn as_slice(&self) -> &[T] {
+        self.prefix.as_slice()
+    }
+
+    #[inline]
+    /// Returns a mutable slice containing the elements pushed so far.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.prefix.as_mut_slice()
+    }
+
+    #[inline]
+    /// Returns the array if the builder is full, or the builder otherwise.
+    pub fn build(self) -> Result<[T; N], Self> {
+        if !self.is_full() {
+            return Err(self);
+        }
+        // # Safety
+        //
+        //   - the builder is full.
+        Ok(unsafe { self.prefix.into_array_unchecked() })
+    }
+}
+
+/// Iterates over the elements pushed so far, in order, e.g., to salvage them
+/// from a builder that was never filled. The elements that are not yielded are
+/// dropped along with the iterator.
+impl<T, const N: usize> IntoIterator for ArrayBuilder<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T, N> {
+        self.prefix.into_iter()
+    }
+}
+
+impl<T, const N: usize> Default for ArrayBuilder<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayBuilder<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayBuilder").field(&self.prefix).finish()
+    }
+}
+
+/// An iterator over arrays filled with successive elements of an iterator.
+///
+/// This is returned by [`ArrayInitIteratorExt::chunk_arrays`].
+#[derive(Debug)]
+pub struct ArrayChunks<I: Iterator, const N: usize> {
+    iterator: I,
+    /// The elements left once `iterator` could no longer fill an array, or
+    /// `None` if it has not run short yet.
+    remainder: Option<ArrayPrefix<I::Item, N>>,
+}
+
+impl<I: Iterator, const N: usize> ArrayChunks<I, N> {
+    #[inline]
+    /// Returns the elements left once the iterator could no longer fill an
+    /// array.
+    ///
+    /// This is empty until the adaptor has returned `None`.
+    pub fn remainder(&self) -> &[I::Item] {
+        match &self.remainder {
+            Some(remainder) => remainder.as_slice(),
+            None => &[],
+        }
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for ArrayChunks<I, N> {
+    type Item = [I::Item; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remainder.is_some() {
+            return None;
+        }
+        let iterator = &mut self.iterator;
+        match try_array_init_recover(|_| iterator.next().ok_or(())) {
+            Ok(chunk) => Some(chunk),
+            Err(((), remainder)) => {
+                self.remainder = Some(remainder);
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remainder.is_some() {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.iterator.size_hint();
+        (lower / N, upper.map(|upper| upper / N))
+    }
+}
+
+/// A by-value iterator over the elements of an [`ArrayPrefix`], or over the
+/// elements left unmapped by [`try_map_array_recover`].
+///
+/// The elements that were not yielded are dropped along with the iterator.
+pub struct IntoIter<T, const N: usize> {
+    array: MaybeUninit<[T; N]>,
+    /// # Safety
+    ///
+    ///   - `array[alive]` must be a slice of init elements, owned by the iterator.
+    alive: Range<usize>,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    #[inline]
+    fn new(array: [T; N]) -> Self {
+        Self {
+            array: MaybeUninit::new(array),
+            alive: 0..N,
+        }
+    }
+
+    #[inline]
+    /// Returns a slice containing the elements that were not yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        // # Safety
+        //
+        //   - the contract of the `alive` field guarantees that this is sound
+        unsafe {
+            &*ptr::slice_from_raw_parts(
+                (self.array.as_ptr() as *const T).add(self.alive.start),
+                self.alive.len(),
+            )
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        // # Safety
+        //
+        //   - `index` is removed from `alive` before the element is read, so
+        //     that it is read at most once.
+        self.alive
+            .next()
+            .map(|index| unsafe { (self.array.as_ptr() as *const T).add(index).read() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.alive.size_hint()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            // # Safety
+            //
+            //   - the contract of the `alive` field guarantees that this is sound
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                (self.array.as_mut_ptr() as *mut T).add(self.alive.start),
+                self.alive.len(),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn seq() {
+        let seq: [usize; 5] = array_init(|i| i);
+        assert_eq!(&[0, 1, 2, 3, 4], &seq);
+    }
+
+    #[test]
+    fn seq_rev() {
+        let mut calls = 0;
+        let seq: [usize; 5] = array_init_rev(|i| {
+            assert_eq!(i, 4 - calls);
+            calls += 1;
+            i
+        });
+        assert_eq!(&[0, 1, 2, 3, 4], &seq);
+    }
+
+    #[test]
+    fn array_init_dp_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 5] = array_init_dp(|i, prev| {
+                    assert_eq!(prev.len(), i);
+                    if i < 3 {
+                        drop_checker.new_element()
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn array_init_clone_no_drop() {
+        use core::cell::Cell;
+
+        struct PanickyClone<'a> {
+            drop_checker: &'a DropChecker,
+            clones: &'a Cell<usize>,
+            _element: drop_checker::Element<'a>,
+        }
+
+        impl Clone for PanickyClone<'_> {
+            fn clone(&self) -> Self {
+                if self.clones.get() == 2 {
+                    panic!();
+                }
+                self.clones.set(self.clones.get() + 1);
+                PanickyClone {
+                    drop_checker: self.drop_checker,
+                    clones: self.clones,
+                    _element: self.drop_checker.new_element(),
+                }
+            }
+        }
+
+        DropChecker::with(|drop_checker| {
+            let clones = Cell::new(0);
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 5] = array_init_clone(PanickyClone {
+                    drop_checker,
+                    clones: &clones,
+                    _element: drop_checker.new_element(),
+                });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn array_init_trait_objects_no_drop() {
+        use std::boxed::Box;
+
+        trait Shape {}
+        impl<T> Shape for T {}
+
+        DropChecker::with(|drop_checker| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 5] = array_init(|i| -> Box<dyn Shape + '_> {
+                    if i < 3 {
+                        Box::new(drop_checker.new_element())
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn array_init_rev_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 5] = array_init_rev(|i| {
+                    if i > 1 {
+                        drop_checker.new_element()
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn try_array_init_rev_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let mut calls = 0;
+            let result: Result<[_; 5], usize> = try_array_init_rev(|i| {
+                assert_eq!(i, 4 - calls);
+                calls += 1;
+                if i > 1 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(i)
+                }
+            });
+            assert_eq!(result.err(), Some(1));
+        });
+    }
+
+    #[test]
+    fn array_init_indexed_bounds() {
+        let bytes: [u8; 256] = array_init_indexed(|i: u8| i);
+        assert_eq!(bytes[255], 255);
+        let empty: [u8; 0] = array_init_indexed(|_: u8| unreachable!());
+        assert_eq!(empty, []);
+        let result = catch_unwind(|| {
+            let _: [u8; 257] = array_init_indexed(|i: u8| i);
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn array_init_prev_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 5] = array_init_prev(drop_checker.new_element(), |i, _| {
+                    if i < 3 {
+                        drop_checker.new_element()
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+            let _: [_; 0] = array_init_prev(drop_checker.new_element(), |_, _| unreachable!());
+        });
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn array_init_2d_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let mut calls = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [[_; 3]; 2] = array_init_2d(|i, j| {
+                    assert_eq!(i * 3 + j, calls);
+                    calls += 1;
+                    if (i, j) < (1, 2) {
+                        drop_checker.new_element()
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+            assert_eq!(calls, 6);
+        });
+    }
+
+    #[test]
+    fn invalid_bit_patterns() {
+        // `bool` and `char` do not need to be dropped, so these go through the
+        // unguarded path, which must never produce a value from uninit memory.
+        let bools: [bool; 8] = array_init(|i| i % 2 == 0);
+        assert_eq!(bools, [true, false, true, false, true, false, true, false]);
+        let chars: [char; 4] = from_iter("abcd".chars()).unwrap();
+        assert_eq!(chars, ['a', 'b', 'c', 'd']);
+        assert_eq!(from_iter::<_, char, 4>("abc".chars()), None);
+    }
+
+    #[test]
+    fn array_from_iter() {
+        let array = [0, 1, 2, 3, 4];
+        let seq: [usize; 5] = from_iter(array.iter().copied()).unwrap();
+        assert_eq!(array, seq,);
+    }
+
+    #[test]
+    fn collect_array() {
+        let array: Option<[usize; 5]> = (0..).collect_array();
+        assert_eq!(array, Some([0, 1, 2, 3, 4]));
+        assert_eq!((0..4).try_collect_array::<5>(), Err(4));
+    }
+
+    #[test]
+    fn array_init_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result: Result<[_; 5], ()> = try_array_init(|i| {
+                if i < 3 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(())
+                }
+            });
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn try_array_init_short_circuits() {
+        DropChecker::with(|drop_checker| {
+            let mut calls = 0;
+            let result: Result<[_; 5], usize> = try_array_init(|i| {
+                calls += 1;
+                if i < 3 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(i)
+                }
+            });
+            assert_eq!(result.err(), Some(3));
+            assert_eq!(calls, 4);
+        });
+    }
+
+    #[test]
+    fn try_array_init_partial_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result: Result<[_; 5], (usize, ())> = try_array_init_partial(|i| {
+                if i < 3 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(())
+                }
+            });
+            assert_eq!(result.err(), Some((3, ())));
+        });
+    }
+
+    #[test]
+    fn try_array_init_recover_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result: Result<[_; 5], _> = try_array_init_recover(|i| {
+                if i < 3 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(())
+                }
+            });
+            let (_, prefix) = result.err().unwrap();
+            assert_eq!(prefix.len(), 3);
+        });
+    }
+
+    #[test]
+    fn from_iter_order() {
+        // A stateful iterator, whose elements depend on the number of calls
+        let mut calls = 0;
+        let iterator = core::iter::from_fn(|| {
+            calls += 1;
+            Some(calls * 10)
+        });
+        let arr: [usize; 5] = from_iter(iterator).unwrap();
+        assert_eq!(arr, [10, 20, 30, 40, 50]);
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn from_iter_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..3).map(|_| drop_checker.new_element());
+            let result: Option<[_; 5]> = from_iter(iterator);
+            assert!(result.is_none());
+        });
+    }
+
+    #[test]
+    fn from_iter_2d_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..5).map(|_| drop_checker.new_element());
+            let result: Option<[[_; 3]; 2]> = from_iter_2d(iterator);
+            assert!(result.is_none());
+        });
+    }
+
+    #[test]
+    fn from_iter_while_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let mut iterator = (0..5).map(|i| (i, drop_checker.new_element())).peekable();
+            let result: Option<[_; 5]> = from_iter_while(&mut iterator, |&(i, _)| i < 3);
+            assert!(result.is_none());
+        });
+    }
+
+    #[test]
+    fn from_iter_while_keeps_failing_element() {
+        let mut iter = (1..10).peekable();
+        let arr: Option<[u32; 3]> = from_iter_while(&mut iter, |&x| x % 4 != 0);
+        assert_eq!(arr, Some([1, 2, 3]));
+        let arr: Option<[u32; 3]> = from_iter_while(&mut iter, |&x| x % 4 != 0);
+        assert_eq!(arr, None);
+        assert_eq!(iter.next(), Some(4));
+        let arr: Option<[u32; 3]> = from_iter_while(&mut iter, |&x| x % 4 != 0);
+        assert_eq!(arr, Some([5, 6, 7]));
+        assert_eq!(iter.next(), Some(8));
+    }
+
+    #[test]
+    fn array_init_until_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let prefix: ArrayPrefix<_, 5> = array_init_until(|i| {
+                if i < 3 {
+                    Some(drop_checker.new_element())
+                } else {
+                    None
+                }
+            });
+            assert_eq!(prefix.len(), 3);
+            let mut iter = prefix.into_iter();
+            assert!(iter.next().is_some());
+            assert_eq!(iter.len(), 2);
+        });
+    }
+
+    #[test]
+    fn from_iter_remainder_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..3).map(|_| drop_checker.new_element());
+            let result: Result<[_; 5], _> = from_iter_remainder(iterator);
+            let (consumed, mut rest) = result.err().unwrap();
+            assert_eq!(consumed, 3);
+            assert!(rest.next().is_none());
+        });
+    }
+
+    #[test]
+    fn from_iter_exact_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..6).map(|_| drop_checker.new_element());
+            let result: Result<[_; 5], _> = from_iter_exact(iterator);
+            assert_eq!(result.err(), Some(ArrayInitError::TooLong { expected: 5 }));
+        });
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn from_iter_debug_checked_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..6).map(|_| drop_checker.new_element());
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: Option<[_; 5]> = from_iter_debug_checked(iterator);
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn try_from_iter_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..5).map(|i| {
+                if i < 3 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(i)
+                }
+            });
+            let result: Result<[_; 5], _> = try_from_iter(iterator);
+            assert_eq!(result.err(), Some(ArrayInitError::Closure(3)));
+        });
+    }
+
+    #[test]
+    fn try_from_iter_exact_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..6).map(|_| Ok::<_, ()>(drop_checker.new_element()));
+            let result: Result<[_; 5], _> = try_from_iter_exact(iterator);
+            assert_eq!(result.err(), Some(ArrayInitError::TooLong { expected: 5 }));
+        });
+    }
+
+    #[test]
+    fn array_init_error_display() {
+        use std::string::ToString;
+
+        let err = from_iter_exact::<_, u32, 3>(0..2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 3 elements, but the iterator yielded 2"
+        );
+        let err = from_iter_exact::<_, u32, 3>(0..5).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 3 elements, but the iterator yielded more"
+        );
+        let err = try_from_iter::<_, u32, _, 3>(std::vec![Ok(0), Err("bad")]).unwrap_err();
+        assert_eq!(err.to_string(), "failed to initialize element: bad");
+        let err = try_from_iter::<_, u32, &str, 3>(std::vec![Ok(0)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 3 elements, but the iterator yielded 1"
+        );
+        let err = try_from_iter_exact::<_, u32, &str, 1>(std::vec![Ok(0), Err("bad")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 1 elements, but the iterator yielded more"
+        );
+    }
+
+    #[test]
+    fn from_iters_zip_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let first = (0..5).map(|_| drop_checker.new_element());
+            let second = (0..3).map(|_| drop_checker.new_element());
+            let result: Result<[_; 5], _> = from_iters_zip_checked(first, second);
+            assert_eq!(result.err(), Some(ZipError::SecondTooShort { got: 3 }));
+        });
+    }
+
+    #[test]
+    fn from_iter_reversed_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let iterator = (0..3).map(|_| drop_checker.new_element());
+            let result: Option<[_; 5]> = from_iter_reversed(iterator);
+            assert!(result.is_none());
+        });
+    }
+
+    #[test]
+    fn chunk_arrays_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let mut chunks = (0..8)
+                .map(|_| drop_checker.new_element())
+                .chunk_arrays::<3>();
+            assert!(chunks.next().is_some());
+            assert!(chunks.next().is_some());
+            assert!(chunks.next().is_none());
+            assert_eq!(chunks.remainder().len(), 2);
+            assert!(chunks.next().is_none());
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn array_init_boxed_large() {
+        let boxed: Box<[u8; 1 << 23]> = array_init_boxed(|i| i as u8);
+        assert!(boxed.iter().enumerate().all(|(i, &x)| x == i as u8));
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "leak-on-panic")))]
+    #[test]
+    fn array_init_boxed_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: Box<[_; 5]> = array_init_boxed(|i| {
+                    if i < 3 {
+                        drop_checker.new_element()
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_array_init_boxed_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result: Result<Box<[_; 5]>, ()> = try_array_init_boxed(|i| {
+                if i < 3 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(())
+                }
+            });
+            assert!(result.is_err());
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn array_init_catch_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result: Result<[_; 5], _> = array_init_catch(|i| {
+                if i < 3 {
+                    drop_checker.new_element()
+                } else {
+                    panic!()
+                }
+            });
+            assert_eq!(result.err().map(|(i, _)| i), Some(3));
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_results_all_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let results = (0..5).map(|i| {
+                let element = drop_checker.new_element();
+                if i % 2 == 1 {
+                    Err(i)
+                } else {
+                    Ok(element)
+                }
+            });
+            let errors = from_results_all::<_, _, _, 5>(results).err().unwrap();
+            assert_eq!(
+                errors,
+                [
+                    (1, ArrayInitError::Closure(1)),
+                    (3, ArrayInitError::Closure(3))
+                ]
+            );
+            let results = (0..5).map(|_| Ok::<_, ()>(drop_checker.new_element()));
+            let array: [_; 5] = from_results_all(results).ok().unwrap();
+            drop(array);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_iter_spill_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let inline = from_iter_spill::<_, _, 5>((0..5).map(|_| drop_checker.new_element()));
+            assert!(!inline.is_spilled());
+            let array = inline.into_array().ok().unwrap();
+            drop(array);
+            let inline = from_iter_spill::<_, _, 5>((0..3).map(|_| drop_checker.new_element()));
+            assert_eq!(inline.len(), 3);
+            let inline = inline.into_array().err().unwrap();
+            drop(inline);
+            let spilled = from_iter_spill::<_, _, 5>((0..8).map(|_| drop_checker.new_element()));
+            assert_eq!(spilled.len(), 8);
+            assert!(spilled.into_array().is_err());
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_vec_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let vec: Vec<_> = (0..5).map(|_| drop_checker.new_element()).collect();
+            let array: [_; 5] = from_vec(vec).ok().unwrap();
+            drop(array);
+            let mut vec: Vec<_> = (0..5).map(|_| drop_checker.new_element()).collect();
+            vec.reserve(10);
+            let boxed: Box<[_; 5]> = from_vec_boxed(vec).ok().unwrap();
+            drop(boxed);
+            let vec: Vec<_> = (0..5).map(|_| drop_checker.new_element()).collect();
+            assert!(from_vec::<_, 4>(vec).is_err());
+        });
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn from_bytes_unaligned() {
+        let bytes: [u8; 9] = array_init(|i| i as u8);
+        let values: Option<[u32; 2]> = from_bytes(&bytes[1..]);
+        assert_eq!(
+            values,
+            Some([
+                u32::from_ne_bytes([1, 2, 3, 4]),
+                u32::from_ne_bytes([5, 6, 7, 8])
+            ])
+        );
+        assert_eq!(from_bytes::<u32, 2>(&bytes), None);
+        assert_eq!(from_bytes::<u32, 0>(&[]), Some([]));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_array_init_seq() {
+        let seq: [usize; 5] = block_on(async_array_init(|i| async move { i }));
+        assert_eq!(&[0, 1, 2, 3, 4], &seq);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_array_init_cancel_no_drop() {
+        use ::core::future::{pending, ready};
+        use std::task::{Context, Poll};
+
+        DropChecker::with(|drop_checker| {
+            let mut future = std::boxed::Box::pin(async_array_init::<_, _, _, 5>(|i| async move {
+                if i < 3 {
+                    ready(drop_checker.new_element()).await
+                } else {
+                    pending().await
+                }
+            }));
+            let waker = noop_waker();
+            let poll = future.as_mut().poll(&mut Context::from_waker(&waker));
+            assert!(matches!(poll, Poll::Pending));
+        });
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_array_init_timeout_no_drop() {
+        use ::core::future::{pending, ready};
+
+        DropChecker::with(|drop_checker| {
+            let result = block_on(async_array_init_timeout::<_, _, _, _, _, 5>(
+                |i| async move {
+                    if i < 2 {
+                        pending().await
+                    }
+                },
+                |i| async move {
+                    if i < 2 {
+                        ready(drop_checker.new_element()).await
+                    } else {
+                        pending().await
+                    }
+                },
+            ));
+            assert_eq!(result.err(), Some((2, TimeoutError)));
+        });
+        let seq: Result<[usize; 3], _> = block_on(async_array_init_timeout(|_| ready(()), ready));
+        assert_eq!(seq, Ok([0, 1, 2]));
+    }
+
+    #[cfg(all(feature = "async", not(feature = "leak-on-panic")))]
+    #[test]
+    fn async_constructors_no_leak_on_panic() {
+        use ::core::future::{pending, ready};
+
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let future = async_array_init::<_, _, _, 5>(|_| ready(next()));
+            expect_panic(|| drop(block_on(future)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let future =
+                async_array_init_timeout::<_, _, _, _, _, 5>(|_| pending(), |_| ready(next()));
+            expect_panic(|| drop(block_on(future)));
+        });
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn from_stream_no_drop() {
+        use std::task::{Context, Poll};
+
+        enum End {
+            Finish,
+            Stall,
+            #[cfg(not(feature = "leak-on-panic"))]
+            Panic,
+        }
+
+        struct Elements<'a> {
+            drop_checker: &'a DropChecker,
+            left: usize,
+            end: End,
+        }
+
+        impl<'a> Stream for Elements<'a> {
+            type Item = drop_checker::Element<'a>;
+
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                if self.left == 0 {
+                    return match self.end {
+                        End::Finish => Poll::Ready(None),
+                        End::Stall => Poll::Pending,
+                        #[cfg(not(feature = "leak-on-panic"))]
+                        End::Panic => panic!("stream panicked"),
+                    };
+                }
+                self.left -= 1;
+                Poll::Ready(Some(self.drop_checker.new_element()))
+            }
+        }
+
+        expect_drops(3, |drop_checker| {
+            let stream = Elements {
+                drop_checker,
+                left: 3,
+                end: End::Finish,
+            };
+            assert!(block_on(from_stream::<_, _, 5>(stream)).is_none());
+        });
+        expect_drops(5, |drop_checker| {
+            let stream = Elements {
+                drop_checker,
+                left: 8,
+                end: End::Finish,
+            };
+            let array = block_on(from_stream::<_, _, 5>(stream)).unwrap();
+            drop(array);
+        });
+        expect_drops(3, |drop_checker| {
+            // Cancelled while the stream is pending
+            let stream = Elements {
+                drop_checker,
+                left: 3,
+                end: End::Stall,
+            };
+            let mut future = std::boxed::Box::pin(from_stream::<_, _, 5>(stream));
+            let waker = noop_waker();
+            let poll = future.as_mut().poll(&mut Context::from_waker(&waker));
+            assert!(poll.is_pending());
+        });
+        #[cfg(not(feature = "leak-on-panic"))]
+        expect_drops(3, |drop_checker| {
+            let stream = Elements {
+                drop_checker,
+                left: 3,
+                end: End::Panic,
+            };
+            expect_panic(|| drop(block_on(from_stream::<_, _, 5>(stream))));
+        });
+    }
+
+    #[cfg(any(feature = "async", feature = "futures"))]
+    fn block_on<Fut: Future>(future: Fut) -> Fut::Output {
+        use std::task::{Context, Poll};
+
+        let mut future = std::boxed::Box::pin(future);
+        let waker = noop_waker();
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                return output;
+            }
+        }
+    }
+
+    #[cfg(any(feature = "async", feature = "futures"))]
+    fn noop_waker() -> std::task::Waker {
+        use std::task::{RawWaker, RawWakerVTable, Waker};
+
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn map_array_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let source: [_; 5] = array_init(|_| drop_checker.new_element());
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 5] =
+                    map_array(source, |i, element| if i < 2 { element } else { panic!() });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_array_length() {
+        use serde::de::value::{Error, SeqDeserializer};
+
+        let deserialize =
+            |len| deserialize_array::<_, u32, 3>(SeqDeserializer::<_, Error>::new(0..len));
+        assert_eq!(deserialize(3), Ok([0, 1, 2]));
+        assert!(deserialize(2).is_err());
+        assert!(deserialize(4).is_err());
+    }
+
+    #[cfg(all(feature = "rayon", not(feature = "leak-on-panic")))]
+    #[test]
+    fn array_init_par_no_drop() {
+        use ::core::sync::atomic::{AtomicUsize, Ordering};
+
+        static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+        struct Element;
+
+        impl Drop for Element {
+            fn drop(&mut self) {
+                LIVE.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let result = catch_unwind(|| {
+            let _: [_; 1000] = array_init_par(|i| {
+                if i == 700 {
+                    panic!();
+                }
+                LIVE.fetch_add(1, Ordering::SeqCst);
+                Element
+            });
+        });
+        assert!(result.is_err());
+        assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn const_array_init() {
+        const SEQ: [usize; 5] = const_array_init!(5, |i| i);
+        assert_eq!(&[0, 1, 2, 3, 4], &SEQ);
+        const EMPTY: [usize; 0] = const_array_init!(0, |i| i);
+        assert_eq!(EMPTY, []);
+
+        const fn double(i: usize) -> usize {
+            2 * i
+        }
+        const DOUBLES: [usize; 4] = const_array_init!(4, double);
+        assert_eq!(DOUBLES, [0, 2, 4, 6]);
+        const ONES: [u32; 4] = const_array_init!(4, usize::count_ones);
+        assert_eq!(ONES, [0, 1, 1, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn chunk_iter_edge_cases() {
+        let (chunks, tail) = chunk_iter::<_, u32, 3>(0..0);
+        assert!(chunks.is_empty() && tail.is_empty());
+        let (chunks, tail) = chunk_iter::<_, u32, 3>(0..6);
+        assert_eq!((chunks.len(), tail.len()), (2, 0));
+        let (chunks, tail) = chunk_iter::<_, u32, 0>(0..2);
+        assert_eq!((chunks.len(), tail.len()), (0, 2));
+    }
+
+    #[test]
+    fn try_map_array_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let source: [_; 5] = array_init(|_| drop_checker.new_element());
+            let result: Result<[_; 5], ()> = try_map_array(source, |i, _| {
+                if i < 2 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(())
+                }
+            });
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn try_map_array_recover_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let source: [_; 5] = array_init(|_| drop_checker.new_element());
+            let result: Result<[_; 5], _> = try_map_array_recover(source, |i, _| {
+                if i < 2 {
+                    Ok(drop_checker.new_element())
+                } else {
+                    Err(())
+                }
+            });
+            let (_, mapped, rest) = result.err().unwrap();
+            assert_eq!((mapped.len(), rest.len()), (2, 2));
+        });
+    }
+
+    #[test]
+    fn array_builder_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let mut builder = ArrayBuilder::<_, 5>::new();
+            for _ in 0..3 {
+                assert!(builder.push(drop_checker.new_element()).is_ok());
+            }
+            let builder = builder.build().err().unwrap();
+            assert_eq!(builder.len(), 3);
+        });
+    }
+
+    #[test]
+    fn array_builder_into_iter() {
+        DropChecker::with(|drop_checker| {
+            let mut builder = ArrayBuilder::<_, 5>::new();
+            for _ in 0..3 {
+                assert!(builder.push(drop_checker.new_element()).is_ok());
             }
-            Ok(array.assume_init())
+            let mut iter = builder.into_iter();
+            assert_eq!(iter.len(), 3);
+            drop(iter.next());
+        });
+        let mut builder = ArrayBuilder::<u32, 5>::new();
+        builder.push(1).unwrap();
+        builder.push(2).unwrap();
+        assert!(builder.into_iter().eq(1..=2));
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn zip_with_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let first: [_; 5] = array_init(|_| drop_checker.new_element());
+            let second: [_; 5] = array_init(|_| drop_checker.new_element());
+            let mut calls = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 5] = zip_with(first, second, |a, b| {
+                    calls += 1;
+                    if calls < 3 {
+                        (a, b, drop_checker.new_element())
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn split_array_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let array: [_; 5] = array_init(|_| drop_checker.new_element());
+            let (head, tail): ([_; 2], [_; 3]) = split_array(array);
+            drop((head, tail));
+
+            let array: [_; 5] = array_init(|_| drop_checker.new_element());
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: ([_; 2], [_; 2]) = split_array(array);
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn concat_arrays_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let first: [_; 2] = array_init(|_| drop_checker.new_element());
+            let second: [_; 3] = array_init(|_| drop_checker.new_element());
+            let array: [_; 5] = concat_arrays(first, second);
+            drop(array);
+
+            let first: [_; 2] = array_init(|_| drop_checker.new_element());
+            let second: [_; 3] = array_init(|_| drop_checker.new_element());
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: [_; 4] = concat_arrays(first, second);
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn array_init_with_aux_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                let _: ([_; 5], [_; 5]) = array_init_with_aux(|i| {
+                    if i < 3 {
+                        (drop_checker.new_element(), drop_checker.new_element())
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn from_be_le_bytes() {
+        let value = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10_u128;
+        assert_eq!(from_be::<_, 16>(value), value.to_be_bytes());
+        assert_eq!(from_le::<_, 16>(value), value.to_le_bytes());
+        assert_eq!(from_be::<_, 8>(-3_i64), (-3_i64).to_be_bytes());
+        assert_eq!(from_le::<_, 1>(0x80_u8), [0x80]);
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn drop_order() {
+        use core::cell::RefCell;
+        use std::vec::Vec;
+
+        struct Recorder<'a> {
+            index: usize,
+            dropped: &'a RefCell<Vec<usize>>,
         }
-    } else {
-        // else: `mem::needs_drop::<T>()`
 
-        /// # Safety
-        ///
-        ///   - `base_ptr[.. initialized_count]` must be a slice of init elements...
-        ///
-        ///   - ... that must be sound to `ptr::drop_in_place` if/when
-        ///     `UnsafeDropSliceGuard` is dropped: "symbolic ownership"
-        struct UnsafeDropSliceGuard<Item> {
-            base_ptr: *mut Item,
-            initialized_count: usize,
+        impl Drop for Recorder<'_> {
+            fn drop(&mut self) {
+                self.dropped.borrow_mut().push(self.index);
+            }
         }
 
-        impl<Item> Drop for UnsafeDropSliceGuard<Item> {
-            fn drop(self: &'_ mut Self) {
-                unsafe {
-                    // # Safety
-                    //
-                    //   - the contract of the struct guarantees that this is sound
-                    ptr::drop_in_place(slice::from_raw_parts_mut(
-                        self.base_ptr,
-                        self.initialized_count,
-                    ));
+        let dropped = RefCell::new(Vec::new());
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _: [_; 5] = array_init(|index| {
+                if index < 3 {
+                    Recorder {
+                        index,
+                        dropped: &dropped,
+                    }
+                } else {
+                    panic!()
+                }
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(dropped.replace(Vec::new()), [0, 1, 2]);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _: [_; 5] = array_init_rev(|index| {
+                if index > 1 {
+                    Recorder {
+                        index,
+                        dropped: &dropped,
+                    }
+                } else {
+                    panic!()
                 }
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(dropped.replace(Vec::new()), [2, 3, 4]);
+    }
+
+    #[test]
+    fn constructors_no_leak() {
+        // Every element created is dropped exactly once, whether the
+        // constructors succeed or fail without panicking.
+        DropChecker::with(|drop_checker| {
+            let new = |_| drop_checker.new_element();
+            let _: [_; 5] = from_fn(new);
+            let _: [_; 5] = array_init_scan((), |_, _| drop_checker.new_element());
+            let _: [_; 5] = array_init_ctx(drop_checker, |d, _| d.new_element());
+            let _: [Option<_>; 5] = array_init_into(new);
+            let _: [[_; 2]; 3] = array_init_2d(|_, _| drop_checker.new_element());
+            let _: Option<[_; 5]> = opt_array_init(|i| if i < 5 { Some(new(i)) } else { None });
+            let _: Option<[_; 5]> = opt_array_init(|i| if i < 3 { Some(new(i)) } else { None });
+            let _: Option<[_; 5]> =
+                array_init_filter_map(10, |i| if i % 2 == 0 { Some(new(i)) } else { None });
+            let _: Option<[_; 5]> =
+                array_init_filter_map(8, |i| if i % 2 == 0 { Some(new(i)) } else { None });
+
+            let _: Option<[_; 5]> = from_iter((0..5).map(new));
+            let _: Option<[_; 5]> = from_iter_indexed(0..5, |_, _| drop_checker.new_element());
+            let _: Option<[_; 5]> = from_iter_indexed(0..3, |_, _| drop_checker.new_element());
+            let _: Option<[_; 5]> = from_exact_iter((0..3).map(new));
+            let _: Option<[_; 5]> = fill_from_iter(&mut (0..7).map(new));
+            let _: (Option<[_; 5]>, _) = from_iter_counted((0..3).map(new));
+            let _: Option<[_; 5]> = from_iter_reversed((0..5).map(new));
+            let _: Result<[_; 5], _> = from_iters_zip_checked((0..5).map(new), (0..5).map(new));
+            let _: Result<[_; 5], _> = from_iters_zip_checked((0..3).map(new), (0..5).map(new));
+
+            let source: [_; 5] = array_init(new);
+            let _: [_; 5] = map_array_init(&source, |_| drop_checker.new_element());
+            let _: [_; 5] = map_array(source, |_, element| element);
+            let _: ([_; 5], [_; 5]) = unzip_array(array_init(|i| (new(i), new(i))));
+            let _: [_; 5] = zip_with(array_init(new), array_init(new), |a, _| a);
+
+            let _: Option<[_; 2]> = array_of_refs(&[new(0), new(1), new(2)]);
+        });
+    }
+
+    /// Runs `f` with a fresh `DropChecker`, and checks that it created exactly
+    /// `created` elements. `DropChecker::with` then checks that each of them
+    /// was dropped exactly once.
+    fn expect_drops(created: usize, f: impl FnOnce(&DropChecker)) {
+        DropChecker::with(|drop_checker| {
+            f(drop_checker);
+            assert_eq!(drop_checker.created(), created);
+        });
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    fn expect_panic(f: impl FnOnce()) {
+        assert!(catch_unwind(AssertUnwindSafe(f)).is_err());
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    /// Returns a generator of `count` elements, which panics once they have all
+    /// been created.
+    fn elements_then_panic<'a>(
+        drop_checker: &'a DropChecker,
+        count: usize,
+    ) -> impl FnMut() -> drop_checker::Element<'a> {
+        let mut left = count;
+        move || {
+            assert!(left > 0, "no elements left");
+            left -= 1;
+            drop_checker.new_element()
+        }
+    }
+
+    /// An element whose clones are elements too, cloned at most `clones_left`
+    /// times before panicking.
+    struct Cloned<'a> {
+        drop_checker: &'a DropChecker,
+        clones_left: &'a Cell<usize>,
+        _element: drop_checker::Element<'a>,
+    }
+
+    impl<'a> Cloned<'a> {
+        fn new(drop_checker: &'a DropChecker, clones_left: &'a Cell<usize>) -> Self {
+            Cloned {
+                drop_checker,
+                clones_left,
+                _element: drop_checker.new_element(),
             }
         }
+    }
 
-        //  If the `initializer(i)` call panics, `panic_guard` is dropped,
-        //  dropping `array[.. initialized_count]` => no memory leak!
-        //
-        // # Safety
-        //
-        //  1. - For D > 0, by construction, array[.. initiliazed_count] only
-        //       contains init elements, thus there is no risk of dropping
-        //       uninit data;
-        //     - For D < 0, by construction, array[N - initialized_count..] only
-        //       contains init elements.
-        //
-        //  2. - for D > 0, we are within the array since we start from the
-        //       beginning of the array, and we have `0 <= i < N`.
-        //     - for D < 0, we start at the end of the array and go back one
-        //       place before writing, going back N times in total, finishing
-        //       at the start of the array.
-        //
-        unsafe {
-            let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
-            // pointer to array = *mut [T; N] <-> *mut T = pointer to first element
-            let mut ptr_i = array.as_mut_ptr() as *mut T;
-            if D < 0 {
-                ptr_i = ptr_i.add(N);
+    impl Clone for Cloned<'_> {
+        fn clone(&self) -> Self {
+            let left = self.clones_left.get();
+            assert!(left > 0, "no clones left");
+            self.clones_left.set(left - 1);
+            Cloned::new(self.drop_checker, self.clones_left)
+        }
+    }
+
+    #[test]
+    fn constructors_no_leak_on_failure() {
+        // `Err`, `None` and short iterators: the elements created before the
+        // failure, and those handed back, are dropped exactly once.
+        use drop_checker::Element;
+
+        fn new_until(drop_checker: &DropChecker, i: usize, end: usize) -> Option<Element<'_>> {
+            if i < end {
+                Some(drop_checker.new_element())
+            } else {
+                None
             }
-            let mut panic_guard = UnsafeDropSliceGuard {
-                base_ptr: ptr_i,
-                initialized_count: 0,
-            };
+        }
+        expect_drops(3, |d| {
+            let _: Result<[_; 5], _> = try_array_init(|i| new_until(d, i, 3).ok_or(()));
+        });
+        expect_drops(3, |d| {
+            let _: Result<[_; 5], _> = try_from_fn(|i| new_until(d, i, 3).ok_or(()));
+        });
+        expect_drops(3, |d| {
+            let _: Result<[_; 5], _> = try_array_init_rev(|i| new_until(d, 4 - i, 3).ok_or(()));
+        });
+        expect_drops(3, |d| {
+            let res: Result<[_; 5], _> = try_array_init_partial(|i| new_until(d, i, 3).ok_or(()));
+            assert_eq!(res.err().map(|(i, ())| i), Some(3));
+        });
+        expect_drops(3, |d| {
+            let res: Result<[_; 5], _> = try_array_init_recover(|i| new_until(d, i, 3).ok_or(()));
+            assert_eq!(res.err().map(|((), prefix)| prefix.len()), Some(3));
+        });
+        expect_drops(3, |d| {
+            let _: Option<[_; 5]> = opt_array_init(|i| new_until(d, i, 3));
+        });
+        expect_drops(3, |d| {
+            let prefix: ArrayPrefix<_, 5> = array_init_until(|i| new_until(d, i, 3));
+            assert_eq!(prefix.len(), 3);
+        });
+        expect_drops(5, |d| {
+            let _: Option<[_; 5]> = array_init_checked(|_| d.new_element(), |_| false);
+        });
 
-            for i in 0..N {
-                // Invariant: `i` elements have already been initialized
-                panic_guard.initialized_count = i;
-                // If this panics or fails, `panic_guard` is dropped, thus
-                // dropping the elements in `base_ptr[.. i]` for D > 0 or
-                // `base_ptr[N - i..]` for D < 0.
-                let value_i = initializer(i)?;
-                // this cannot panic
-                // the previously uninit value is overwritten without being read or dropped
-                if D < 0 {
-                    ptr_i = ptr_i.sub(1);
-                    panic_guard.base_ptr = ptr_i;
-                }
-                ptr_i.write(value_i);
-                if D > 0 {
-                    ptr_i = ptr_i.add(1);
-                }
+        expect_drops(3, |d| {
+            let results = (0..5).map(|i| new_until(d, i, 3).ok_or(()));
+            let _: Result<[_; 5], _> = try_from_iter(results);
+        });
+        expect_drops(2, |d| {
+            let _: Result<[_; 5], ArrayInitError<()>> =
+                try_from_iter((0..2).map(|_| Ok(d.new_element())));
+        });
+        expect_drops(6, |d| {
+            let results = (0..6).map(|_| Ok::<_, ()>(d.new_element()));
+            let _: Result<[_; 5], _> = try_from_iter_exact(results);
+        });
+        expect_drops(2, |d| {
+            let results = (0..2).map(|_| Ok::<_, ()>(d.new_element()));
+            let _: Result<[_; 5], _> = try_from_iter_exact(results);
+        });
+        expect_drops(6, |d| {
+            let _: Result<[_; 5], _> = from_iter_exact((0..6).map(|_| d.new_element()));
+        });
+        expect_drops(3, |d| {
+            let _: Result<[_; 5], _> = from_iter_exact((0..3).map(|_| d.new_element()));
+        });
+        expect_drops(3, |d| {
+            let _: Option<[_; 5]> = from_iter((0..3).map(|_| d.new_element()));
+        });
+        expect_drops(4, |d| {
+            let _: Option<[[_; 3]; 2]> = from_iter_2d((0..4).map(|_| d.new_element()));
+        });
+        expect_drops(5, |d| {
+            // The element failing the predicate is left in the iterator
+            let mut iterator = (0..5).map(|_| d.new_element()).peekable();
+            let mut calls = 0;
+            let _: Option<[_; 5]> = from_iter_while(&mut iterator, |_| {
+                calls += 1;
+                calls < 4
+            });
+            assert_eq!(iterator.count(), 2);
+        });
+        expect_drops(3, |d| {
+            let res: Result<[_; 5], _> = from_iter_remainder((0..3).map(|_| d.new_element()));
+            assert_eq!(res.err().map(|(consumed, _)| consumed), Some(3));
+        });
+        expect_drops(3, |d| {
+            let _: Option<([_; 5], _)> = from_iter_with_rest((0..3).map(|_| d.new_element()));
+        });
+        expect_drops(3, |d| {
+            let _: (Option<[_; 5]>, _) = from_iter_counted((0..3).map(|_| d.new_element()));
+        });
+        expect_drops(3, |d| {
+            let _: Option<[_; 5]> = from_iter_reversed((0..3).map(|_| d.new_element()));
+        });
+        expect_drops(0, |d| {
+            // Too short to be consumed at all
+            let _: Option<[_; 5]> = from_exact_iter((0..3).map(|_| d.new_element()));
+        });
+        expect_drops(6, |d| {
+            let first = (0..3).map(|_| d.new_element());
+            let second = (0..5).map(|_| d.new_element());
+            let _: Option<[_; 5]> = from_iters_zip(first, second);
+        });
+        expect_drops(7, |d| {
+            let first = (0..5).map(|_| d.new_element());
+            let second = (0..3).map(|_| d.new_element());
+            let _: Result<[_; 5], _> = from_iters_zip_checked(first, second);
+        });
+
+        expect_drops(3, |d| {
+            let clones_left = Cell::new(5);
+            let source: [_; 3] = array_init(|_| Cloned::new(d, &clones_left));
+            let _: Option<[_; 5]> = from_slice(&source);
+            assert_eq!(clones_left.get(), 5);
+        });
+        expect_drops(6, |d| {
+            let mut builder = ArrayBuilder::<_, 5>::new();
+            for _ in 0..5 {
+                assert!(builder.push(d.new_element()).is_ok());
             }
-            // From now on, the code can no longer `panic!`, let's take the
-            // symbolic ownership back
-            mem::forget(panic_guard);
+            assert!(builder.push(d.new_element()).is_err());
+        });
+        expect_drops(3, |d| {
+            let mut builder = ArrayBuilder::<_, 5>::new();
+            for _ in 0..3 {
+                assert!(builder.push(d.new_element()).is_ok());
+            }
+            assert!(builder.build().is_err());
+        });
+        expect_drops(7, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let _: Result<[_; 5], _> = try_map_array(source, |i, _| new_until(d, i, 2).ok_or(()));
+        });
+        expect_drops(7, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let res: Result<[_; 5], _> =
+                try_map_array_recover(source, |i, _| new_until(d, i, 2).ok_or(()));
+            assert_eq!(
+                res.err()
+                    .map(|((), mapped, rest)| (mapped.len(), rest.len())),
+                Some((2, 2))
+            );
+        });
+        expect_drops(5, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let (head, tail): ([_; 2], [_; 3]) = split_array(source);
+            let _: [_; 5] = concat_arrays(head, tail);
+        });
 
-            Ok(array.assume_init())
+        #[cfg(feature = "alloc")]
+        {
+            expect_drops(3, |d| {
+                let _: Result<Box<[_; 5]>, _> =
+                    try_array_init_boxed(|i| new_until(d, i, 3).ok_or(()));
+            });
+            expect_drops(3, |d| {
+                let results = (0..5).map(|i| {
+                    if i % 2 == 0 {
+                        Ok(d.new_element())
+                    } else {
+                        Err(i)
+                    }
+                });
+                let errors = from_results_all::<_, _, _, 5>(results).err().unwrap();
+                assert_eq!(errors.len(), 2);
+            });
+            expect_drops(3, |d| {
+                let vec: Vec<_> = (0..3).map(|_| d.new_element()).collect();
+                assert!(from_vec::<_, 5>(vec).is_err());
+            });
+            expect_drops(3, |d| {
+                let vec: Vec<_> = (0..3).map(|_| d.new_element()).collect();
+                assert!(from_vec_boxed::<_, 5>(vec).is_err());
+            });
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn constructors_no_leak_on_panic() {
+        // The initializer, the iterator or a clone panics after creating a few
+        // elements: these, and the source elements, are dropped exactly once.
+        // `array_init_fn_table` is left out, as a function pointer cannot
+        // borrow the checker; it only forwards to `array_init`.
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init(|_| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(from_fn(|_| next())));
+        });
+        expect_drops(3, |d| {
+            let init = |i| {
+                assert!(i < 3);
+                d.new_element()
+            };
+            expect_panic(|| drop::<[_; 5]>(array_init_fn(init)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_rev(|_| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_dir::<_, _, true, 5>(|_| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_scan((), |_, _| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_len(|_, _| next())));
+        });
+        expect_drops(3, |d| {
+            let hook = |i| assert!(i < 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_with_hook(|_| d.new_element(), hook)));
+        });
+        expect_drops(5, |d| {
+            let check = |_: &[_; 5]| panic!();
+            expect_panic(|| drop(array_init_checked(|_| d.new_element(), check)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_ctx(&(), |_, _| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_token(|_| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_prev(next(), |_, _| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_dp(|_, _| next())));
+        });
+        expect_drops(3, |d| {
+            let clones_left = Cell::new(2);
+            let value = Cloned::new(d, &clones_left);
+            expect_panic(|| drop::<[_; 5]>(array_init_clone(value)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[Option<_>; 5]>(array_init_into(|_| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(array_init_indexed(|_: u8| next())));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[[_; 2]; 3]>(array_init_2d(|_, _| next())));
+        });
+        expect_drops(3, |d| {
+            // The first element of the third pair is dropped while unwinding
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<([_; 5], [_; 5])>(array_init_with_aux(|_| (next(), next()))));
+        });
+        expect_drops(7, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let mut next = elements_then_panic(d, 2);
+            let windows = |_: &[_; 2]| next();
+            expect_panic(|| drop::<[_; 4]>(array_init_windows(&source, windows)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let mut slot = MaybeUninit::<[_; 5]>::uninit();
+            expect_panic(|| {
+                array_init_in_place(&mut slot, |_| next());
+            });
+        });
+
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<Result<[_; 5], ()>>(try_array_init(|_| Ok(next()))));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<Result<[_; 5], ()>>(try_from_fn(|_| Ok(next()))));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<Result<[_; 5], ()>>(try_array_init_rev(|_| Ok(next()))));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| {
+                drop::<Result<[_; 5], (usize, ())>>(try_array_init_partial(|_| Ok(next())))
+            });
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| {
+                drop::<Result<[_; 5], _>>(try_array_init_recover(|_| Ok::<_, ()>(next())))
+            });
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<Option<[_; 5]>>(opt_array_init(|_| Some(next()))));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<Option<[_; 5]>>(array_init_filter_map(10, |_| Some(next()))));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<ArrayPrefix<_, 5>>(array_init_until(|_| Some(next()))));
+        });
+
+        expect_drops(8, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(map_array_init(&source, |_| next())));
+        });
+        expect_drops(8, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(map_array(source, |_, _| next())));
+        });
+        expect_drops(8, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<Result<[_; 5], ()>>(try_map_array(source, |_, _| Ok(next()))));
+        });
+        expect_drops(8, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| {
+                drop::<Result<[_; 5], _>>(try_map_array_recover(source, |_, _| Ok::<_, ()>(next())))
+            });
+        });
+        expect_drops(13, |d| {
+            let first: [_; 5] = array_init(|_| d.new_element());
+            let second: [_; 5] = array_init(|_| d.new_element());
+            let mut next = elements_then_panic(d, 3);
+            expect_panic(|| drop::<[_; 5]>(zip_with(first, second, |_, _| next())));
+        });
+        expect_drops(5, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            expect_panic(|| {
+                array_fold(source, 0, |count, _| {
+                    assert!(count < 2);
+                    count + 1
+                });
+            });
+        });
+        expect_drops(5, |d| {
+            let source: [_; 5] = array_init(|_| d.new_element());
+            expect_panic(|| drop::<([_; 2], [_; 2])>(split_array(source)));
+        });
+        expect_drops(4, |d| {
+            let head: [_; 2] = array_init(|_| d.new_element());
+            let tail: [_; 2] = array_init(|_| d.new_element());
+            expect_panic(|| drop::<[_; 5]>(concat_arrays(head, tail)));
+        });
+        expect_drops(7, |d| {
+            let clones_left = Cell::new(2);
+            let source: [_; 5] = array_init(|_| Cloned::new(d, &clones_left));
+            expect_panic(|| drop::<Option<[_; 5]>>(from_slice(&source)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let mut builder = ArrayBuilder::<_, 5>::new();
+            expect_panic(|| loop {
+                assert!(builder.push(next()).is_ok());
+            });
+        });
+
+        // The iterators panic when pulling their fourth element
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Option<[_; 5]>>(from_iter(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Option<[[_; 3]; 2]>>(from_iter_2d(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Option<[_; 5]>>(from_iter_indexed(iterator, |_, e| e)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..5).map(|_| next());
+            expect_panic(|| drop::<Option<[_; 5]>>(from_exact_iter(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let mut iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Option<[_; 5]>>(fill_from_iter(&mut iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let mut iterator = (0..).map(|_| next()).peekable();
+            expect_panic(|| drop::<Option<[_; 5]>>(from_iter_while(&mut iterator, |_| true)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Result<[_; 5], _>>(from_iter_remainder(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Option<([_; 5], _)>>(from_iter_with_rest(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<(Option<[_; 5]>, _)>(from_iter_counted(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Result<[_; 5], _>>(from_iter_exact(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Option<[_; 5]>>(from_iter_debug_checked(iterator)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let results = (0..).map(|_| Ok::<_, ()>(next()));
+            expect_panic(|| drop::<Result<[_; 5], _>>(try_from_iter(results)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let results = (0..).map(|_| Ok::<_, ()>(next()));
+            expect_panic(|| drop::<Result<[_; 5], _>>(try_from_iter_exact(results)));
+        });
+        expect_drops(6, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let first = (0..).map(|_| next());
+            let second = (0..).map(|_| d.new_element());
+            expect_panic(|| drop::<Option<[_; 5]>>(from_iters_zip(first, second)));
+        });
+        expect_drops(6, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let first = (0..).map(|_| next());
+            let second = (0..).map(|_| d.new_element());
+            expect_panic(|| drop::<Result<[_; 5], _>>(from_iters_zip_checked(first, second)));
+        });
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let iterator = (0..).map(|_| next());
+            expect_panic(|| drop::<Option<[_; 5]>>(from_iter_reversed(iterator)));
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            expect_drops(3, |d| {
+                let mut next = elements_then_panic(d, 3);
+                expect_panic(|| drop::<Box<[_; 5]>>(array_init_boxed(|_| next())));
+            });
+            expect_drops(3, |d| {
+                let mut next = elements_then_panic(d, 3);
+                expect_panic(|| {
+                    drop::<Result<Box<[_; 5]>, ()>>(try_array_init_boxed(|_| Ok(next())))
+                });
+            });
+            expect_drops(3, |d| {
+                let mut next = elements_then_panic(d, 3);
+                expect_panic(|| drop::<Pin<Box<[_; 5]>>>(array_init_pinned(|_| next())));
+            });
+            expect_drops(3, |d| {
+                let mut next = elements_then_panic(d, 3);
+                let iterator = (0..).map(|_| next());
+                expect_panic(|| drop(chunk_iter::<_, _, 2>(iterator)));
+            });
+            expect_drops(3, |d| {
+                let mut next = elements_then_panic(d, 3);
+                let results = (0..).map(|_| Ok::<_, ()>(next()));
+                expect_panic(|| drop(from_results_all::<_, _, _, 5>(results)));
+            });
+            expect_drops(3, |d| {
+                let mut next = elements_then_panic(d, 3);
+                let iterator = (0..).map(|_| next());
+                expect_panic(|| drop(from_iter_spill::<_, _, 5>(iterator)));
+            });
+        }
+        #[cfg(feature = "std")]
+        expect_drops(3, |d| {
+            let mut next = elements_then_panic(d, 3);
+            let res: Result<[_; 5], _> = array_init_catch(|_| next());
+            assert_eq!(res.err().map(|(i, _)| i), Some(3));
+        });
+    }
 
     #[test]
-    fn seq() {
-        let seq: [usize; 5] = array_init(|i| i);
-        assert_eq!(&[0, 1, 2, 3, 4], &seq);
+    fn zero_length() {
+        // Neither the initializers nor the iterators may be called
+        fn never<T>(_: usize) -> T {
+            panic!("the initializer was called")
+        }
+        fn never_iter<T>() -> impl Iterator<Item = T> {
+            core::iter::from_fn(|| panic!("the iterator was polled"))
+        }
+
+        let _: [u8; 0] = array_init(never);
+        let _: [u8; 0] = array_init_rev(never);
+        let _: [u8; 0] = array_init_scan((), |i, _| never(i));
+        let _: [u8; 0] = array_init_prev(0, |i, _| never(i));
+        let _: [std::string::String; 0] = array_init_clone(std::string::String::new());
+        let _: [u8; 0] = array_init_indexed(|i: u8| never(i as usize));
+        let _: [[u8; 0]; 2] = array_init_2d(|i, _| never(i));
+        let _: [[u8; 2]; 0] = array_init_2d(|i, _| never(i));
+        let _: ([u8; 0], [u8; 0]) = array_init_with_aux(never);
+        assert_eq!(try_array_init::<(), _, u8, 0>(never), Ok([]));
+        assert_eq!(opt_array_init::<_, u8, 0>(never), Some([]));
+        assert!(array_init_until::<_, u8, 0>(never).is_empty());
+
+        assert_eq!(from_iter::<_, u8, 0>(never_iter()), Some([]));
+        assert_eq!(from_iter_reversed::<_, u8, 0>(never_iter()), Some([]));
+        assert_eq!(
+            from_iter_while::<_, _, u8, 0>(&mut never_iter().peekable(), |_| true),
+            Some([])
+        );
+        assert_eq!(from_iter_counted::<_, u8, 0>(never_iter()), (Some([]), 0));
+        assert_eq!(try_from_iter::<_, u8, (), 0>(never_iter()), Ok([]));
+        assert_eq!(from_iter_exact::<_, u8, 0>(0..0), Ok([]));
+        assert_eq!(
+            from_iter_exact::<_, u8, 0>(0..1),
+            Err(ArrayInitError::TooLong { expected: 0 })
+        );
+        assert_eq!(
+            from_iters_zip::<_, _, u8, u8, 0>(never_iter(), never_iter()),
+            Some([])
+        );
+
+        let _: [u8; 0] = map_array([0u8; 0], |i, _| never(i));
+        let _: [u8; 0] = zip_with([0u8; 0], [0u8; 0], |_, _| never(0));
+        let _: ([u8; 0], [u8; 0]) = unzip_array([]);
+        let _: ([u8; 0], [u8; 2]) = split_array([1, 2]);
+        let _: [u8; 2] = concat_arrays([], [1, 2]);
+        assert_eq!(from_slice::<u8, 0>(&[]), Some([]));
+        assert_eq!(array_of_refs::<u8, 0>(&[1]), Some([]));
+        let _: [MaybeUninit<u8>; 0] = uninit_array();
+
+        #[cfg(feature = "alloc")]
+        {
+            let _: Box<[std::string::String; 0]> = array_init_boxed(never);
+            let _: Result<Box<[u8; 0]>, ()> = try_array_init_boxed(never);
+        }
     }
 
     #[test]
-    fn array_from_iter() {
-        let array = [0, 1, 2, 3, 4];
-        let seq: [usize; 5] = from_iter(array.iter().copied()).unwrap();
-        assert_eq!(array, seq,);
+    fn resize_array_no_drop() {
+        DropChecker::with(|drop_checker| {
+            let array: [_; 5] = array_init(|_| Some(drop_checker.new_element()));
+            let shorter: [_; 3] = resize_array(array);
+            let longer: [_; 6] = resize_array(shorter);
+            assert!(longer[3..].iter().all(Option::is_none));
+            assert!(reshape::<_, 6, 5>(longer).is_none());
+        });
     }
 
     #[test]
-    fn array_init_no_drop() {
+    fn array_fold_no_drop() {
         DropChecker::with(|drop_checker| {
-            let result: Result<[_; 5], ()> = try_array_init(|i| {
-                if i < 3 {
-                    Ok(drop_checker.new_element())
-                } else {
-                    Err(())
-                }
-            });
+            let array: [_; 5] = array_init(|_| drop_checker.new_element());
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                array_fold(
+                    array,
+                    0,
+                    |count, _| if count < 2 { count + 1 } else { panic!() },
+                )
+            }));
             assert!(result.is_err());
         });
     }
 
     #[test]
-    fn from_iter_no_drop() {
+    fn range_array_bounds() {
+        assert_eq!(range_array::<u8, 6>(250), [250, 251, 252, 253, 254, 255]);
+        assert_eq!(range_array::<i8, 0>(127), []);
+        assert!(catch_unwind(|| range_array::<u8, 7>(250)).is_err());
+    }
+
+    #[cfg(not(feature = "leak-on-panic"))]
+    #[test]
+    fn array_init_in_place_no_drop() {
         DropChecker::with(|drop_checker| {
-            let iterator = (0..3).map(|_| drop_checker.new_element());
-            let result: Option<[_; 5]> = from_iter(iterator);
-            assert!(result.is_none());
+            let mut slot = MaybeUninit::<[_; 5]>::uninit();
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                array_init_in_place(&mut slot, |i| {
+                    if i < 3 {
+                        drop_checker.new_element()
+                    } else {
+                        panic!()
+                    }
+                });
+            }));
+            assert!(result.is_err());
         });
     }
 
     #[test]
-    fn from_iter_reversed_no_drop() {
-        DropChecker::with(|drop_checker| {
-            let iterator = (0..3).map(|_| drop_checker.new_element());
-            let result: Option<[_; 5]> = from_iter_reversed(iterator);
-            assert!(result.is_none());
+    fn array_init_token_indices() {
+        let mut squares = [0, 1, 4, 9];
+        let arr: [_; 4] = array_init_token(|i| {
+            *i.of_mut(&mut squares) += 1;
+            (i.get(), *i.of(&squares))
+        });
+        assert_eq!(arr, [(0, 1), (1, 2), (2, 5), (3, 10)]);
+    }
+
+    #[test]
+    fn atomics() {
+        use ::core::sync::atomic::{AtomicUsize, Ordering};
+
+        assert_fast_path!([AtomicUsize; 64]);
+        let counters: [AtomicUsize; 64] = array_init_default();
+        assert!(counters.iter().all(|c| c.load(Ordering::Relaxed) == 0));
+        let counters: [AtomicUsize; 4] = array_init(AtomicUsize::new);
+        assert_eq!(counters[3].load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn array_init_windows_lengths() {
+        let source = [1, 2, 3, 4];
+        let sums: [u32; 2] = array_init_windows(&source, |window: &[_; 3]| window.iter().sum());
+        assert_eq!(sums, [6, 9]);
+        let copies: [u32; 4] = array_init_windows(&source, |&[x]| x);
+        assert_eq!(copies, source);
+        let whole: [[u32; 4]; 1] = array_init_windows(&source, |&window| window);
+        assert_eq!(whole, [source]);
+        let res = catch_unwind(|| {
+            let _: [u32; 2] = array_init_windows(&source, |&[x, _]| x);
         });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let units: [(); 5] = array_init(|_| ());
+        assert_eq!(units, [(); 5]);
+        let units: Result<[(); 5], usize> = try_array_init(|i| if i < 3 { Ok(()) } else { Err(i) });
+        assert_eq!(units, Err(3));
+    }
+
+    #[test]
+    fn zero_sized_elements_drop_count() {
+        use ::core::sync::atomic::{AtomicUsize, Ordering};
+
+        // Only used by this test, so that tests running in parallel do not
+        // interfere.
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Zst;
+        impl Drop for Zst {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = || DROPS.swap(0, Ordering::Relaxed);
+        drop::<[Zst; 5]>(array_init(|_| Zst));
+        assert_eq!(drops(), 5);
+        let res: Result<[Zst; 5], ()> = try_array_init(|i| if i < 3 { Ok(Zst) } else { Err(()) });
+        assert!(res.is_err());
+        assert_eq!(drops(), 3);
+        let res: Result<[Zst; 5], ()> =
+            try_array_init_rev(|i| if i > 1 { Ok(Zst) } else { Err(()) });
+        assert!(res.is_err());
+        assert_eq!(drops(), 3);
+        #[cfg(not(feature = "leak-on-panic"))]
+        {
+            let res = catch_unwind(|| {
+                let _: [Zst; 5] = array_init(|i| if i < 3 { Zst } else { panic!() });
+            });
+            assert!(res.is_err());
+            assert_eq!(drops(), 3);
+        }
+        #[cfg(feature = "alloc")]
+        {
+            drop::<Box<[Zst; 5]>>(array_init_boxed(|_| Zst));
+            assert_eq!(drops(), 5);
+        }
     }
 
     #[test]
@@ -450,7 +6504,7 @@ mod tests {
         }
 
         impl Drop for Element<'_> {
-            fn drop(self: &'_ mut Self) {
+            fn drop(&mut self) {
                 assert!(self.slot.replace(false), "Double free!");
             }
         }
@@ -462,7 +6516,12 @@ mod tests {
                 drop_checker.assert_no_leaks();
             }
 
-            pub(super) fn new_element(self: &'_ Self) -> Element<'_> {
+            /// Returns the number of elements created so far.
+            pub(super) fn created(&self) -> usize {
+                self.next_uninit_slot.get()
+            }
+
+            pub(super) fn new_element(&self) -> Element<'_> {
                 let i = self.next_uninit_slot.get();
                 self.next_uninit_slot.set(i + 1);
                 self.slots[i].set(true);
@@ -478,7 +6537,7 @@ mod tests {
                 }
             }
 
-            fn assert_no_leaks(self: Self) {
+            fn assert_no_leaks(self) {
                 let leak_count: usize = self.slots[..self.next_uninit_slot.get()]
                     .iter()
                     .map(|slot| usize::from(slot.get() as u8))